@@ -1,9 +1 @@
-pub mod config;
 pub mod consts;
-pub mod feature;
-pub mod kfold;
-pub mod loader;
-pub mod metrics;
-pub mod model;
-pub mod runner;
-pub mod submission;
@@ -0,0 +1,592 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+
+use crate::config::{AbstractConfig, Config, OutputConfig};
+use crate::feature::{AbstractFeatureTransformer, FeaturePipeline};
+use crate::fingerprint::{DataFingerprint, FileFingerprint};
+use crate::kfold::build_kfold;
+use crate::loader::{
+    has_survived_column, load_test_data, load_test_data_lenient, load_train_data,
+    load_train_data_lenient, InputData,
+};
+use crate::metrics::accuracy;
+use crate::model::{build_model, check_missing};
+use crate::prediction::StoredPredictions;
+use crate::profile::{GroupBy, Profile};
+use crate::runner::{AbstractRunner, GbdtRunner, PredictionResult};
+use crate::submission::{generate_submission, load_sample_submission_ids};
+use crate::utils::{calc_vec_mean, convert_probability_to_label};
+use crate::validation::validate;
+
+pub const TEST_PREDICTIONS_FILE_NAME: &str = "test.csv";
+pub const OOF_PREDICTIONS_FILE_NAME: &str = "oof.csv";
+
+#[derive(Debug, Parser)]
+#[command(about = "Run a Titanic experiment")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Evaluate the experiment with cross-validation without saving anything
+    Cv(CommonArgs),
+    /// Run cross-validation, save the fold models and store their predictions
+    Train(CommonArgs),
+    /// Score a CSV with the saved fold models
+    Predict(PredictArgs),
+    /// Build a Kaggle submission from stored test predictions
+    Submit(SubmitArgs),
+    /// Report every invalid value in the data instead of stopping at the first one
+    Check(DataArgs),
+    /// Describe the train and test data: summaries, value counts and survival rates
+    Profile(ProfileArgs),
+    /// Write the features of the data to Parquet, for inspection in other tools
+    #[cfg(feature = "parquet")]
+    Features(DataArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct CommonArgs {
+    /// Experiment config (TOML or JSON); defaults to the config built into the binary
+    #[arg(long, env = "TITANIC_CONFIG")]
+    pub config: Option<PathBuf>,
+    /// Directory holding train.csv, test.csv and gender_submission.csv
+    #[arg(long, env = "TITANIC_DATA_DIR")]
+    pub data_dir: Option<PathBuf>,
+    /// Directory where models, predictions and submissions are written
+    #[arg(long, env = "TITANIC_OUTPUT_DIR")]
+    pub output_dir: Option<PathBuf>,
+    /// Seed of the fold split
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Number of folds
+    #[arg(long)]
+    pub folds: Option<usize>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct PredictArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+    /// CSV to score, in the train or test format; defaults to the configured test data
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct SubmitArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+    /// Stored predictions to submit; defaults to the test predictions of `train`
+    #[arg(long)]
+    pub predictions: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct DataArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+    /// Data in the train or test format; defaults to the configured train and test data
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ProfileArgs {
+    #[command(flatten)]
+    pub common: CommonArgs,
+    /// One or two columns to compute survival rates by, e.g. `sex` or `pclass,sex`; repeatable
+    #[arg(long)]
+    pub group_by: Vec<GroupBy>,
+    /// Print the profile as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl CommonArgs {
+    /// Loads `--config` (or the TOML `default_config`) and applies the flag overrides.
+    pub fn load_config(&self, default_config: &str) -> Result<Config> {
+        let mut config = match &self.config {
+            Some(path) => Config::from_path(path)?,
+            None => Config::from_toml_str(default_config)?,
+        };
+        if let Some(data_dir) = &self.data_dir {
+            config.data.dir = data_dir.clone();
+        }
+        if let Some(output_dir) = &self.output_dir {
+            config.output.dir = output_dir.clone();
+        }
+        if let Some(seed) = self.seed {
+            config.cv.seed = Some(seed);
+        }
+        if let Some(folds) = self.folds {
+            config.cv.n_splits = folds;
+        }
+        config.validate().context("invalid command-line options")?;
+        Ok(config)
+    }
+}
+
+/// Parses the command line and runs the subcommand.
+///
+/// `default_config` is the TOML config used when neither `--config` nor `TITANIC_CONFIG` is set.
+/// Directories are resolved from the flags, then the `TITANIC_*` environment variables, then the
+/// config; relative paths are relative to the working directory.
+pub fn run(default_config: &str) -> Result<()> {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Cv(args) => cv(&args.load_config(default_config)?),
+        Command::Train(args) => train(&args.load_config(default_config)?),
+        Command::Predict(args) => predict(
+            &args.common.load_config(default_config)?,
+            args.input.as_deref(),
+        ),
+        Command::Submit(args) => submit(
+            &args.common.load_config(default_config)?,
+            args.predictions.as_deref(),
+        ),
+        Command::Check(args) => check(
+            &args.common.load_config(default_config)?,
+            args.input.as_deref(),
+        ),
+        Command::Profile(args) => profile(
+            &args.common.load_config(default_config)?,
+            &args.group_by,
+            args.json,
+        ),
+        #[cfg(feature = "parquet")]
+        Command::Features(args) => features(
+            &args.common.load_config(default_config)?,
+            args.input.as_deref(),
+        ),
+    }
+}
+
+pub fn cv(config: &Config) -> Result<()> {
+    run_cv(config, false)?;
+    Ok(())
+}
+
+pub fn train(config: &Config) -> Result<()> {
+    config.save(config.output.config_path())?;
+    let (fingerprint, test, prediction_results) = run_cv(config, true)?;
+    fingerprint.save(config.output.fingerprint_path())?;
+
+    let test_predictions = StoredPredictions::new(
+        test.iter().map(|x| x.passenger_id).collect(),
+        prediction_results
+            .iter()
+            .map(|r| r.pred_test.clone())
+            .collect(),
+    )?;
+    let test_predictions_path = config
+        .output
+        .prediction_dir()
+        .join(TEST_PREDICTIONS_FILE_NAME);
+    test_predictions.save(&test_predictions_path)?;
+
+    let mut oof_passenger_ids = vec![];
+    let mut oof_predictions = vec![];
+    for r in prediction_results.iter() {
+        oof_passenger_ids.extend(r.valid_passenger_id.iter().copied());
+        oof_predictions.extend(r.pred_valid.iter().copied());
+    }
+    StoredPredictions::new(oof_passenger_ids, vec![oof_predictions])?.save(
+        config
+            .output
+            .prediction_dir()
+            .join(OOF_PREDICTIONS_FILE_NAME),
+    )?;
+    println!("Saved predictions to {}", test_predictions_path.display());
+    Ok(())
+}
+
+pub fn predict(config: &Config, input: Option<&Path>) -> Result<()> {
+    let input = input.map_or_else(|| config.data.test_path(), Path::to_path_buf);
+    let data = if has_survived_column(&input)? {
+        load_train_data(&input)?
+    } else {
+        load_test_data(&input)?
+    };
+    validate(&data, &[], &config.validation).enforce(config.validation.on_violation)?;
+    warn_if_trained_on_other_data(config)?;
+
+    let mut predictions = vec![];
+    for fold in 0..config.cv.n_splits {
+        let model_path = config.output.model_path(fold);
+        let feature_transformer_path = config.output.feature_transformer_path(fold);
+        for path in [&model_path, &feature_transformer_path] {
+            if !path.exists() {
+                bail!("model file {} not found; run `train` first", path.display());
+            }
+        }
+        let mut feature_transformer = FeaturePipeline::from_config(&config.features)?;
+        feature_transformer.load(&feature_transformer_path.to_string_lossy())?;
+        let features = feature_transformer.transform(&data)?;
+        let mut model = build_model(&config.model)?;
+        model.load(&model_path.to_string_lossy())?;
+        check_missing(model.as_ref(), &features)?;
+        predictions.push(model.predict(&features)?);
+    }
+    let stored_predictions =
+        StoredPredictions::new(data.iter().map(|x| x.passenger_id).collect(), predictions)?;
+
+    if data.iter().all(|x| x.survived.is_some()) {
+        let labels = data
+            .iter()
+            .map(|x| x.survived.unwrap() as f64)
+            .collect::<Vec<_>>();
+        println!(
+            "Accuracy: {:?}",
+            accuracy(&labels, &stored_predictions.mean())?
+        );
+    }
+
+    let output_path = config
+        .output
+        .prediction_dir()
+        .join(output_file_name(&input, "csv")?);
+    stored_predictions.save(&output_path)?;
+    config.save(OutputConfig::config_path_for(&output_path))?;
+    println!("Saved predictions to {}", output_path.display());
+    Ok(())
+}
+
+pub fn submit(config: &Config, predictions: Option<&Path>) -> Result<()> {
+    let predictions_path = predictions.map_or_else(
+        || {
+            config
+                .output
+                .prediction_dir()
+                .join(TEST_PREDICTIONS_FILE_NAME)
+        },
+        Path::to_path_buf,
+    );
+    let stored_predictions = StoredPredictions::load(&predictions_path)?;
+
+    let sample_submission_path = config.data.sample_submission_path();
+    let sample_passenger_ids = load_sample_submission_ids(&sample_submission_path)?;
+    if sample_passenger_ids != stored_predictions.passenger_ids {
+        bail!(
+            "passengers in {} do not match {}",
+            predictions_path.display(),
+            sample_submission_path.display()
+        );
+    }
+
+    let pred_test_label = convert_probability_to_label(stored_predictions.mean());
+    let submission_path = config.output.submission_path();
+    generate_submission(
+        pred_test_label,
+        sample_submission_path,
+        submission_path.clone(),
+    )?;
+    config.save(OutputConfig::config_path_for(&submission_path))?;
+    println!("Saved submission to {}", submission_path.display());
+    Ok(())
+}
+
+pub fn check(config: &Config, input: Option<&Path>) -> Result<()> {
+    let inputs = input_paths(config, input);
+    let mut clean = true;
+    let mut datasets = vec![];
+    for input in inputs.iter() {
+        let (data, report) = if has_survived_column(input)? {
+            load_train_data_lenient(input)?
+        } else {
+            load_test_data_lenient(input)?
+        };
+        print!("{}: {}", input.display(), report);
+        clean &= report.is_clean();
+        datasets.push(data);
+    }
+    let (train, test) = match datasets.as_slice() {
+        [train, test] => (train.as_slice(), test.as_slice()),
+        _ => (datasets[0].as_slice(), &[][..]),
+    };
+    let report = validate(train, test, &config.validation);
+    print!("{}", report);
+    clean &= report.is_clean();
+    if !clean {
+        bail!("data quality check failed");
+    }
+    Ok(())
+}
+
+/// Profiles the configured data, grouping by [`GroupBy::defaults`] if `group_bys` is empty.
+pub fn profile(config: &Config, group_bys: &[GroupBy], json: bool) -> Result<()> {
+    let train = load_train_data(config.data.train_path())?;
+    let test = load_test_data(config.data.test_path())?;
+    let group_bys = if group_bys.is_empty() {
+        GroupBy::defaults()
+    } else {
+        group_bys.to_vec()
+    };
+    let profile = Profile::new(&train, &test, &group_bys);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&profile)?);
+    } else {
+        print!("{}", profile);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "parquet")]
+pub fn features(config: &Config, input: Option<&Path>) -> Result<()> {
+    let mut feature_transformer = FeaturePipeline::from_config(&config.features)?;
+    let train = load_train_data(config.data.train_path())?;
+    let test = load_test_data(config.data.test_path())?;
+    feature_transformer.fit_unlabeled(&[train.as_slice(), &test].concat())?;
+    feature_transformer.fit(&train)?;
+    for input in input_paths(config, input).iter() {
+        let data = if has_survived_column(input)? {
+            load_train_data(input)?
+        } else {
+            load_test_data(input)?
+        };
+        validate(&data, &[], &config.validation).enforce(config.validation.on_violation)?;
+        let features = feature_transformer.transform(&data)?;
+        let output_path = config
+            .output
+            .feature_dir()
+            .join(output_file_name(input, "parquet")?);
+        crate::feature::save_features(&output_path, &features)?;
+        config.save(OutputConfig::config_path_for(&output_path))?;
+        println!("Saved features to {}", output_path.display());
+    }
+    Ok(())
+}
+
+fn run_cv(
+    config: &Config,
+    save_models: bool,
+) -> Result<(DataFingerprint, Vec<InputData>, Vec<PredictionResult>)> {
+    let train = load_train_data(config.data.train_path())?;
+    let labels = train
+        .iter()
+        .map(|input_data| input_data.survived.unwrap())
+        .collect::<Vec<u32>>();
+    let test = load_test_data(config.data.test_path())?;
+    validate(&train, &test, &config.validation).enforce(config.validation.on_violation)?;
+    let sample_submission_path = config.data.sample_submission_path();
+    let fingerprint = DataFingerprint {
+        train: FileFingerprint::new(config.data.train_path(), &train)?,
+        test: FileFingerprint::new(config.data.test_path(), &test)?,
+        // Only `submit` needs the sample submission.
+        sample_submission: load_sample_submission_ids(&sample_submission_path)
+            .ok()
+            .map(|ids| FileFingerprint::new(&sample_submission_path, &ids))
+            .transpose()?,
+    };
+    println!("Train data: {}", fingerprint.train);
+    println!("Test data: {}", fingerprint.test);
+    if let Some(sample_submission) = &fingerprint.sample_submission {
+        println!("Sample submission: {}", sample_submission);
+    }
+
+    let feature_transformer = FeaturePipeline::from_config(&config.features)?;
+    let kfold = build_kfold(&config.cv);
+    let model = build_model(&config.model)?;
+    let mut runner = GbdtRunner::new(config.clone(), Box::new(feature_transformer), kfold, model);
+    runner.set_save_models(save_models);
+    let prediction_results = runner.run_cv(&train, labels, &test)?;
+
+    let mut valid_labels = vec![];
+    let mut pred_valids = vec![];
+    let mut feature_importances = vec![];
+    for r in prediction_results.iter() {
+        valid_labels.extend(r.valid_label.iter().clone());
+        pred_valids.extend(r.pred_valid.iter().clone());
+        feature_importances.push(r.feature_importances.clone());
+    }
+
+    let acc = accuracy(&valid_labels, &pred_valids)?;
+    println!("CV Accuracy: {:?}", acc);
+
+    let feature_importances_mean = calc_vec_mean(feature_importances);
+    println!("Feature names: {:?}", &prediction_results[0].feature_names);
+    println!("Feature importances: {:?}", &feature_importances_mean);
+
+    Ok((fingerprint, test, prediction_results))
+}
+
+/// Warns for each configured input file that differs from the one the saved models were
+/// trained with.
+///
+/// Models saved without a fingerprint, and files that cannot be loaded, are not checked.
+fn warn_if_trained_on_other_data(config: &Config) -> Result<()> {
+    let fingerprint_path = config.output.fingerprint_path();
+    if !fingerprint_path.exists() {
+        return Ok(());
+    }
+    let saved = DataFingerprint::load(&fingerprint_path)?;
+    let (train_path, test_path, sample_submission_path) = (
+        config.data.train_path(),
+        config.data.test_path(),
+        config.data.sample_submission_path(),
+    );
+    let current = [
+        load_train_data(&train_path)
+            .ok()
+            .map(|data| FileFingerprint::new(&train_path, &data)),
+        load_test_data(&test_path)
+            .ok()
+            .map(|data| FileFingerprint::new(&test_path, &data)),
+        load_sample_submission_ids(&sample_submission_path)
+            .ok()
+            .map(|ids| FileFingerprint::new(&sample_submission_path, &ids)),
+    ];
+    let saved = [
+        Some(&saved.train),
+        Some(&saved.test),
+        saved.sample_submission.as_ref(),
+    ];
+    for (saved, current) in saved.into_iter().zip(current) {
+        let (Some(saved), Some(current)) = (saved, current.transpose()?) else {
+            continue;
+        };
+        if !saved.same_data(&current) {
+            eprintln!(
+                "warning: the models in {} were trained on different data\n  trained on {}\n  current   {}",
+                config.output.model_dir().display(),
+                saved,
+                current
+            );
+        }
+    }
+    Ok(())
+}
+
+/// `input`, or the configured train and test data.
+fn input_paths(config: &Config, input: Option<&Path>) -> Vec<PathBuf> {
+    match input {
+        Some(input) => vec![input.to_path_buf()],
+        None => vec![config.data.train_path(), config.data.test_path()],
+    }
+}
+
+/// File name of `input` without `.gz` and with `extension`, e.g. `test.csv` for `test.parquet`.
+fn output_file_name(input: &Path, extension: &str) -> Result<PathBuf> {
+    let file_name = input
+        .file_name()
+        .context("input path has no file name")?
+        .to_string_lossy();
+    let file_name = file_name.strip_suffix(".gz").unwrap_or(&file_name);
+    Ok(Path::new(file_name).with_extension(extension))
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    #[rstest]
+    fn test_Cli_parse_common_args() {
+        let cli = Cli::try_parse_from([
+            "exp",
+            "cv",
+            "--data-dir",
+            "data",
+            "--seed",
+            "7",
+            "--folds",
+            "3",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::Cv(args) => {
+                assert_eq!(args.data_dir, Some(PathBuf::from("data")));
+                assert_eq!(args.seed, Some(7));
+                assert_eq!(args.folds, Some(3));
+                assert_eq!(args.config, None);
+            }
+            command => panic!("unexpected command: {:?}", command),
+        }
+    }
+
+    #[rstest]
+    fn test_Cli_parse_predict_args() {
+        let cli = Cli::try_parse_from([
+            "exp",
+            "predict",
+            "--input",
+            "new.csv",
+            "--output-dir",
+            "out",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::Predict(args) => {
+                assert_eq!(args.input, Some(PathBuf::from("new.csv")));
+                assert_eq!(args.common.output_dir, Some(PathBuf::from("out")));
+            }
+            command => panic!("unexpected command: {:?}", command),
+        }
+    }
+
+    #[rstest]
+    fn test_Cli_parse_profile_args() {
+        let cli = Cli::try_parse_from([
+            "exp",
+            "profile",
+            "--group-by",
+            "sex",
+            "--group-by",
+            "pclass,sex",
+            "--json",
+        ])
+        .unwrap();
+        let Command::Profile(args) = cli.command else {
+            panic!("unexpected command");
+        };
+        assert_eq!(
+            args.group_by,
+            vec![
+                "sex".parse::<GroupBy>().unwrap(),
+                "pclass,sex".parse().unwrap()
+            ]
+        );
+        assert!(args.json);
+        assert!(Cli::try_parse_from(["exp", "profile", "--group-by", "deck"]).is_err());
+    }
+
+    #[rstest]
+    #[case("input/new.csv", "csv", "new.csv")]
+    #[case("input/test.csv.gz", "csv", "test.csv")]
+    #[case("input/titanic.zip/train.csv", "parquet", "train.parquet")]
+    #[case("input/test.parquet", "csv", "test.csv")]
+    fn test_output_file_name(#[case] input: &str, #[case] extension: &str, #[case] expected: &str) {
+        let actual = output_file_name(Path::new(input), extension).unwrap();
+        assert_eq!(actual, PathBuf::from(expected));
+    }
+
+    #[rstest]
+    fn test_CommonArgs_load_config_overrides() {
+        let default_config = toml::to_string_pretty(&Config::new()).unwrap();
+
+        let cli = Cli::try_parse_from(["exp", "train", "--folds", "1"]).unwrap();
+        let Command::Train(args) = cli.command else {
+            panic!("unexpected command");
+        };
+        let actual = args.load_config(&default_config).err().unwrap();
+        assert_eq!(
+            format!("{:#}", actual),
+            "invalid command-line options: cv.n_splits must be at least 2, got 1"
+        );
+
+        let cli =
+            Cli::try_parse_from(["exp", "train", "--seed", "0", "--output-dir", "out"]).unwrap();
+        let Command::Train(args) = cli.command else {
+            panic!("unexpected command");
+        };
+        let actual = args.load_config(&default_config).unwrap();
+        assert_eq!(actual.cv.seed, Some(0));
+        assert_eq!(actual.output.dir, PathBuf::from("out"));
+    }
+}
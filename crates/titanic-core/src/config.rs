@@ -0,0 +1,515 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::feature::{
+    builtin_blocks, EncoderConfig, GroupSurvivalConfig, ImputeConfig, FEATURE_NAMES,
+};
+use crate::model::ModelType;
+
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+
+pub trait AbstractConfig: Sized {
+    fn new() -> Self;
+    fn load<P: AsRef<Path>>(&mut self, path: P) -> Result<()>;
+    fn save<P: AsRef<Path>>(&self, path: P) -> Result<()>;
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub data: DataConfig,
+    pub cv: CvConfig,
+    pub features: FeatureConfig,
+    pub model: ModelConfig,
+    pub output: OutputConfig,
+    #[serde(default)]
+    pub validation: ValidationConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DataConfig {
+    pub dir: PathBuf,
+    pub train_file: String,
+    pub test_file: String,
+    pub sample_submission_file: String,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CvStrategy {
+    KFold,
+    Stratified,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CvConfig {
+    pub strategy: CvStrategy,
+    pub n_splits: usize,
+    pub shuffle: bool,
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeatureConfig {
+    pub names: Vec<String>,
+    /// Imputation of missing values by column, e.g. `[features.impute.age]`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub impute: BTreeMap<String, ImputeConfig>,
+    /// Encoder blocks by name, e.g. `[features.encoders.sex_onehot]`; list the name in `names`
+    /// to use one.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub encoders: BTreeMap<String, EncoderConfig>,
+    #[serde(default)]
+    pub group_survival: GroupSurvivalConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelConfig {
+    #[serde(rename = "type")]
+    pub model_type: ModelType,
+    /// Whether the backend splits categorical features natively instead of as ordered codes.
+    #[serde(default)]
+    pub native_categorical: bool,
+    pub params: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputConfig {
+    pub dir: PathBuf,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OnViolation {
+    /// Fail before features are built.
+    Error,
+    /// Print the violations and continue.
+    Warn,
+}
+
+/// Domain rules checked on the loaded data; see [`crate::validation`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationConfig {
+    pub min_age: f64,
+    pub max_age: f64,
+    pub min_fare: f64,
+    pub max_sibsp: i32,
+    pub max_parch: i32,
+    /// Passenger ids must be unique within each file.
+    pub unique_passenger_id: bool,
+    /// Train and test must not share passenger ids.
+    pub disjoint_passenger_ids: bool,
+    pub on_violation: OnViolation,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            min_age: 0.0,
+            max_age: 100.0,
+            min_fare: 0.0,
+            max_sibsp: 10,
+            max_parch: 10,
+            unique_passenger_id: true,
+            disjoint_passenger_ids: true,
+            on_violation: OnViolation::Error,
+        }
+    }
+}
+
+impl DataConfig {
+    pub fn train_path(&self) -> PathBuf {
+        self.dir.join(&self.train_file)
+    }
+
+    pub fn test_path(&self) -> PathBuf {
+        self.dir.join(&self.test_file)
+    }
+
+    pub fn sample_submission_path(&self) -> PathBuf {
+        self.dir.join(&self.sample_submission_file)
+    }
+}
+
+impl OutputConfig {
+    pub fn model_dir(&self) -> PathBuf {
+        self.dir.join("models")
+    }
+
+    /// Model file of the 0-indexed `fold`.
+    pub fn model_path(&self, fold: usize) -> PathBuf {
+        self.model_dir().join(format!("fold{}.dat", fold + 1))
+    }
+
+    /// Fitted feature transformer of the 0-indexed `fold`.
+    pub fn feature_transformer_path(&self, fold: usize) -> PathBuf {
+        self.model_dir()
+            .join(format!("fold{}_features.json", fold + 1))
+    }
+
+    pub fn prediction_dir(&self) -> PathBuf {
+        self.dir.join("predictions")
+    }
+
+    pub fn feature_dir(&self) -> PathBuf {
+        self.dir.join("features")
+    }
+
+    pub fn submission_path(&self) -> PathBuf {
+        self.dir.join("submissions").join("submission.csv")
+    }
+
+    /// Where the fingerprints of the data the models were trained on are saved.
+    pub fn fingerprint_path(&self) -> PathBuf {
+        self.dir.join("fingerprint.json")
+    }
+
+    /// Where the copy of the config used by `train` is saved.
+    pub fn config_path(&self) -> PathBuf {
+        self.dir.join(CONFIG_FILE_NAME)
+    }
+
+    /// Where the copy of the config used to write `output` is saved: next to it, named
+    /// `<stem>.config.toml`.
+    pub fn config_path_for(output: &Path) -> PathBuf {
+        let stem = output.file_stem().unwrap_or_default().to_string_lossy();
+        output.with_file_name(format!("{}.{}", stem, CONFIG_FILE_NAME))
+    }
+}
+
+impl Config {
+    /// Parses a TOML or JSON (by `.json` extension) config.
+    ///
+    /// Relative `data.dir` and `output.dir` are kept as is, i.e. relative to the working directory.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read config file {}", path.display()))?;
+        let config: Config = if is_json(path) {
+            serde_json::from_str(&text)
+                .with_context(|| format!("failed to parse config file {}", path.display()))?
+        } else {
+            toml::from_str(&text)
+                .with_context(|| format!("failed to parse config file {}", path.display()))?
+        };
+        config
+            .validate()
+            .with_context(|| format!("invalid config file {}", path.display()))?;
+        Ok(config)
+    }
+
+    /// Parses a TOML config embedded in a binary, e.g. with `include_str!`.
+    pub fn from_toml_str(text: &str) -> Result<Self> {
+        let config: Config = toml::from_str(text).context("failed to parse embedded config")?;
+        config.validate().context("invalid embedded config")?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        for (key, file) in [
+            ("data.train_file", &self.data.train_file),
+            ("data.test_file", &self.data.test_file),
+            (
+                "data.sample_submission_file",
+                &self.data.sample_submission_file,
+            ),
+        ] {
+            if file.is_empty() {
+                bail!("{} must not be empty", key);
+            }
+        }
+        if self.cv.n_splits < 2 {
+            bail!("cv.n_splits must be at least 2, got {}", self.cv.n_splits);
+        }
+        if self.features.names.is_empty() {
+            bail!("features.names must not be empty");
+        }
+        let builtins = builtin_blocks();
+        for (name, encoder) in self.features.encoders.iter() {
+            if builtins.contains(&name.as_str()) {
+                bail!("encoder {:?} has the name of a built-in feature", name);
+            }
+            encoder
+                .validate()
+                .with_context(|| format!("invalid features.encoders.{}", name))?;
+        }
+        let mut seen = HashSet::new();
+        for name in self.features.names.iter() {
+            if !builtins.contains(&name.as_str()) && !self.features.encoders.contains_key(name) {
+                bail!(
+                    "unknown feature {:?} in features.names; available features: {:?}",
+                    name,
+                    builtins
+                        .iter()
+                        .copied()
+                        .chain(self.features.encoders.keys().map(String::as_str))
+                        .collect::<Vec<_>>()
+                );
+            }
+            if !seen.insert(name) {
+                bail!("duplicate feature {:?} in features.names", name);
+            }
+        }
+        for (column, impute) in self.features.impute.iter() {
+            let column = column.parse().map_err(|e: String| anyhow!(e))?;
+            impute
+                .validate(column)
+                .with_context(|| format!("invalid features.impute.{}", column))?;
+        }
+        if self.features.group_survival.smoothing < 0.0 {
+            bail!(
+                "features.group_survival.smoothing must not be negative, got {}",
+                self.features.group_survival.smoothing
+            );
+        }
+        if self.features.group_survival.n_splits < 2 {
+            bail!(
+                "features.group_survival.n_splits must be at least 2, got {}",
+                self.features.group_survival.n_splits
+            );
+        }
+        if !self.model.params.is_object() {
+            bail!("model.params must be a table");
+        }
+        if self.validation.min_age > self.validation.max_age {
+            bail!(
+                "validation.min_age must not exceed validation.max_age, got {} > {}",
+                self.validation.min_age,
+                self.validation.max_age
+            );
+        }
+        Ok(())
+    }
+}
+
+impl AbstractConfig for Config {
+    fn new() -> Self {
+        Self {
+            data: DataConfig {
+                dir: PathBuf::from("input/titanic"),
+                train_file: "train.csv".to_string(),
+                test_file: "test.csv".to_string(),
+                sample_submission_file: "gender_submission.csv".to_string(),
+            },
+            cv: CvConfig {
+                strategy: CvStrategy::Stratified,
+                n_splits: 5,
+                shuffle: true,
+                seed: Some(42),
+            },
+            features: FeatureConfig {
+                names: FEATURE_NAMES.iter().map(|name| name.to_string()).collect(),
+                impute: BTreeMap::new(),
+                encoders: BTreeMap::new(),
+                group_survival: GroupSurvivalConfig::default(),
+            },
+            model: ModelConfig {
+                model_type: ModelType::LightGBM,
+                native_categorical: false,
+                params: json!({
+                    "objective": "binary",
+                    "metric": "binary_logloss",
+                    "num_leaves": 31,
+                    "learning_rate": 0.05,
+                    "feature_fraction": 0.9,
+                    "bagging_fraction": 0.8,
+                    "bagging_freq": 5,
+                    "verbose": -1,
+                }),
+            },
+            output: OutputConfig {
+                dir: PathBuf::from("output"),
+            },
+            validation: ValidationConfig::default(),
+        }
+    }
+
+    fn load<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        *self = Config::from_path(path)?;
+        Ok(())
+    }
+
+    fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let text = if is_json(path) {
+            serde_json::to_string_pretty(self)?
+        } else {
+            toml::to_string_pretty(self)?
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, text)
+            .with_context(|| format!("failed to write config file {}", path.display()))
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    use crate::feature::{Column, ImputeStrategy};
+    use crate::test_utils::temp_path;
+
+    #[rstest]
+    #[case("predictions/new.csv", "predictions/new.config.toml")]
+    #[case("submissions/submission.csv", "submissions/submission.config.toml")]
+    fn test_OutputConfig_config_path_for(#[case] output: &str, #[case] expected: &str) {
+        let actual = OutputConfig::config_path_for(&Path::new("output").join(output));
+        assert_eq!(actual, Path::new("output").join(expected));
+    }
+
+    #[rstest]
+    #[case("config.toml")]
+    #[case("config.json")]
+    fn test_Config_save_load(#[case] name: &str) {
+        let path = temp_path("config", name);
+        let mut expected = Config::new();
+        expected.cv.strategy = CvStrategy::KFold;
+        expected.model.model_type = ModelType::XGBoost;
+        expected.validation.on_violation = OnViolation::Warn;
+        expected.features.impute.insert(
+            "age".to_string(),
+            ImputeConfig {
+                strategy: ImputeStrategy::GroupedMedian,
+                group_by: vec![Column::Title, Column::Pclass],
+                indicator: true,
+            },
+        );
+        expected.features.encoders.insert(
+            "embarked_target".to_string(),
+            EncoderConfig::Target {
+                column: Column::Embarked,
+                smoothing: 5.0,
+                n_splits: 3,
+                seed: 1,
+            },
+        );
+        expected
+            .features
+            .names
+            .extend(["cabin".to_string(), "embarked_target".to_string()]);
+        expected.features.group_survival.unknown = Some(0.5);
+        expected.save(&path).unwrap();
+
+        let mut actual = Config::new();
+        actual.load(&path).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(|c: &mut Config| c.cv.n_splits = 1, "cv.n_splits must be at least 2, got 1")]
+    #[case(|c: &mut Config| c.features.names.clear(), "features.names must not be empty")]
+    #[case(
+        |c: &mut Config| c.features.names.push("age".to_string()),
+        "duplicate feature \"age\" in features.names"
+    )]
+    #[case(|c: &mut Config| c.model.params = json!(1), "model.params must be a table")]
+    #[case(|c: &mut Config| c.data.train_file.clear(), "data.train_file must not be empty")]
+    #[case(
+        |c: &mut Config| {
+            c.features.impute.insert(
+                "age".to_string(),
+                ImputeConfig {
+                    strategy: ImputeStrategy::GroupedMedian,
+                    group_by: vec![],
+                    indicator: false,
+                },
+            );
+        },
+        "invalid features.impute.age"
+    )]
+    #[case(
+        |c: &mut Config| {
+            c.features.encoders.insert(
+                "sex".to_string(),
+                EncoderConfig::Count { column: Column::Sex, normalize: false },
+            );
+        },
+        "encoder \"sex\" has the name of a built-in feature"
+    )]
+    #[case(
+        |c: &mut Config| {
+            c.features.encoders.insert(
+                "embarked_target".to_string(),
+                EncoderConfig::Target {
+                    column: Column::Embarked,
+                    smoothing: 10.0,
+                    n_splits: 1,
+                    seed: 0,
+                },
+            );
+        },
+        "invalid features.encoders.embarked_target"
+    )]
+    #[case(
+        |c: &mut Config| c.features.group_survival.smoothing = -1.0,
+        "features.group_survival.smoothing must not be negative, got -1"
+    )]
+    #[case(
+        |c: &mut Config| c.features.group_survival.n_splits = 1,
+        "features.group_survival.n_splits must be at least 2, got 1"
+    )]
+    #[case(
+        |c: &mut Config| c.validation.min_age = 120.0,
+        "validation.min_age must not exceed validation.max_age, got 120 > 100"
+    )]
+    fn test_Config_validate(#[case] modify: fn(&mut Config), #[case] expected: &str) {
+        let mut config = Config::new();
+        modify(&mut config);
+        let actual = config.validate().err().unwrap();
+        assert_eq!(actual.to_string(), expected);
+    }
+
+    #[rstest]
+    fn test_Config_validate_unknown_feature() {
+        let mut config = Config::new();
+        config.features.names = vec!["deck".to_string()];
+        let actual = config.validate().err().unwrap();
+        assert!(actual
+            .to_string()
+            .starts_with("unknown feature \"deck\" in features.names"));
+    }
+
+    #[rstest]
+    fn test_Config_from_toml_str() {
+        let text = toml::to_string_pretty(&Config::new()).unwrap();
+        assert_eq!(Config::from_toml_str(&text).unwrap(), Config::new());
+    }
+
+    #[rstest]
+    fn test_Config_from_toml_str_without_validation() {
+        let mut config = Config::new();
+        config.validation.max_age = 80.0;
+        let text = toml::to_string_pretty(&config).unwrap();
+        let (text, _) = text.split_once("[validation]").unwrap();
+        assert_eq!(Config::from_toml_str(text).unwrap(), Config::new());
+    }
+
+    #[rstest]
+    fn test_Config_from_path_unknown_field() {
+        let path = temp_path("config", "unknown_field.toml");
+        let text = toml::to_string_pretty(&Config::new()).unwrap() + "\n[extra]\nkey = 1\n";
+        fs::write(&path, text).unwrap();
+        let actual = Config::from_path(&path).err().unwrap();
+        assert!(format!("{:#}", actual).contains("unknown field `extra`"));
+    }
+}
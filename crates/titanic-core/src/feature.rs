@@ -0,0 +1,355 @@
+use std::collections::BTreeMap;
+use std::fs;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::loader::InputData;
+
+mod cabin;
+mod column;
+#[cfg(feature = "parquet")]
+mod columnar;
+mod encode;
+mod group;
+mod impute;
+mod matrix;
+mod pipeline;
+
+pub use cabin::CabinTransformer;
+pub use column::{Column, CATEGORICAL_COLUMNS, NUMERIC_COLUMNS};
+#[cfg(feature = "parquet")]
+pub use columnar::{features_to_record_batch, save_features};
+pub use encode::{CountEncoder, EncoderConfig, OneHotEncoder, OrdinalEncoder, TargetEncoder};
+pub use group::{GroupSurvivalConfig, GroupSurvivalTransformer, GroupTransformer};
+pub use impute::{FittedImputer, ImputeConfig, ImputeStrategy, Imputer, IMPUTABLE_COLUMNS};
+pub use matrix::{FeatureField, FeatureMatrix, FeatureSchema, FeatureType, MISSING};
+pub use pipeline::{builtin_blocks, FeaturePipeline};
+
+pub trait AbstractFeatureTransformer {
+    /// Learns what `transform` needs from the training data, e.g. a training fold.
+    fn fit(&mut self, input_data: &[InputData]) -> Result<()>;
+    /// Learns label-free statistics from train and test together, once before the folds are
+    /// fitted; does nothing by default.
+    fn fit_unlabeled(&mut self, _input_data: &[InputData]) -> Result<()> {
+        Ok(())
+    }
+    fn transform(&self, input_data: &[InputData]) -> Result<FeatureMatrix>;
+    /// The fitted state.
+    fn state(&self) -> Result<serde_json::Value>;
+    fn set_state(&mut self, state: serde_json::Value) -> Result<()>;
+
+    /// Saves the fitted state as JSON.
+    fn save(&self, path: &str) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(&self.state()?)?)
+            .with_context(|| format!("failed to write {}", path))
+    }
+
+    fn load(&mut self, path: &str) -> Result<()> {
+        let text = fs::read_to_string(path).with_context(|| format!("failed to read {}", path))?;
+        let state =
+            serde_json::from_str(&text).with_context(|| format!("failed to parse {}", path))?;
+        self.set_state(state)
+            .with_context(|| format!("failed to load {}", path))
+    }
+}
+
+pub const FEATURE_NAMES: [&str; 9] = [
+    "pclass",
+    "sex",
+    "age",
+    "sibsp",
+    "parch",
+    "fare",
+    "embarked",
+    "title",
+    "family_size",
+];
+
+/// Builds a subset of [`FEATURE_NAMES`], in the given order, followed by the missing indicators
+/// of the imputers.
+///
+/// Missing values of imputed columns are filled by their [`Imputer`]; other missing values
+/// are [`MISSING`].
+pub struct FeatureTransformer {
+    indices: Vec<usize>,
+    imputers: Vec<Imputer>,
+}
+
+impl FeatureTransformer {
+    pub fn new(names: &[String]) -> Result<Self> {
+        let indices = names
+            .iter()
+            .map(|name| {
+                FEATURE_NAMES
+                    .iter()
+                    .position(|feature_name| feature_name == name)
+                    .ok_or_else(|| anyhow!("unknown feature: {}", name))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            indices,
+            imputers: vec![],
+        })
+    }
+
+    pub fn with_imputer(mut self, imputer: Imputer) -> Self {
+        self.imputers.push(imputer);
+        self
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.fields().into_iter().map(|field| field.name).collect()
+    }
+
+    fn fields(&self) -> Vec<FeatureField> {
+        let mut fields = self
+            .indices
+            .iter()
+            .map(|i| match FEATURE_NAMES[*i] {
+                name @ ("age" | "fare") => FeatureField::float(name),
+                name @ ("pclass" | "sex" | "embarked" | "title") => FeatureField::categorical(name),
+                name => FeatureField::integer(name),
+            })
+            .collect::<Vec<_>>();
+        fields.extend(
+            self.imputers
+                .iter()
+                .filter_map(Imputer::indicator_name)
+                .map(FeatureField::binary),
+        );
+        fields
+    }
+
+    /// The imputed value of `column`, or `column`'s value with [`MISSING`] for missing values.
+    fn value(&self, column: Column, input_data: &InputData) -> Result<f64> {
+        match self
+            .imputers
+            .iter()
+            .find(|imputer| imputer.column == column)
+        {
+            Some(imputer) => imputer.impute(input_data),
+            None => Ok(column.numeric(input_data).unwrap_or(MISSING)),
+        }
+    }
+}
+
+/// What [`FeatureTransformer`] saves: the selected features and the fitted imputers by column.
+#[derive(Debug, Serialize, Deserialize)]
+struct FittedState {
+    names: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    imputers: BTreeMap<String, FittedImputer>,
+}
+
+impl Default for FeatureTransformer {
+    fn default() -> Self {
+        Self {
+            indices: (0..FEATURE_NAMES.len()).collect(),
+            imputers: vec![],
+        }
+    }
+}
+
+impl AbstractFeatureTransformer for FeatureTransformer {
+    fn fit(&mut self, input_data: &[InputData]) -> Result<()> {
+        for imputer in self.imputers.iter_mut() {
+            imputer.fit(input_data)?;
+        }
+        Ok(())
+    }
+
+    fn transform(&self, input_data: &[InputData]) -> Result<FeatureMatrix> {
+        let mut matrix =
+            FeatureMatrix::with_capacity(FeatureSchema::new(self.fields())?, input_data.len());
+        let mut features = Vec::with_capacity(matrix.n_cols());
+        for input_data in input_data.iter() {
+            let all_features = [
+                input_data.pclass.map_or(MISSING, |p| p as i32 as f64),
+                input_data.sex.map_or(MISSING, |s| s as i32 as f64),
+                self.value(Column::Age, input_data)?,
+                input_data.sibsp.map_or(MISSING, f64::from),
+                input_data.parch.map_or(MISSING, f64::from),
+                self.value(Column::Fare, input_data)?,
+                input_data.embarked.map_or(MISSING, |s| s as i32 as f64),
+                input_data
+                    .name
+                    .as_ref()
+                    .and_then(|name| name.title)
+                    .map_or(MISSING, |title| title as i32 as f64),
+                match (input_data.sibsp, input_data.parch) {
+                    (Some(sibsp), Some(parch)) => (sibsp + parch + 1) as f64,
+                    _ => MISSING,
+                },
+            ];
+            features.clear();
+            features.extend(self.indices.iter().map(|i| all_features[*i]));
+            for imputer in self.imputers.iter().filter(|i| i.config.indicator) {
+                features.push(f64::from(imputer.column.numeric(input_data).is_none()));
+            }
+            matrix.push_row(&features);
+        }
+        Ok(matrix)
+    }
+
+    fn state(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::to_value(FittedState {
+            names: self.names(),
+            imputers: self
+                .imputers
+                .iter()
+                .filter_map(|imputer| {
+                    let fitted = imputer.fitted()?.clone();
+                    Some((imputer.column.to_string(), fitted))
+                })
+                .collect(),
+        })?)
+    }
+
+    /// Fails if the state was saved for other features than the selected ones.
+    fn set_state(&mut self, state: serde_json::Value) -> Result<()> {
+        let mut state: FittedState = serde_json::from_value(state)?;
+        if state.names != self.names() {
+            bail!(
+                "the state was fitted for features {:?}, but {:?} are selected",
+                state.names,
+                self.names()
+            );
+        }
+        for imputer in self.imputers.iter_mut() {
+            let fitted = state
+                .imputers
+                .remove(&imputer.column.to_string())
+                .ok_or_else(|| anyhow!("the state has no fitted {} imputer", imputer.column))?;
+            imputer.set_fitted(fitted);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    use crate::loader::PassengerName;
+    use crate::test_utils::{fixture_input_data, temp_path};
+
+    #[rstest]
+    fn test_FeatureTransformer_transform_selected(fixture_input_data: InputData) {
+        let names = vec!["fare".to_string(), "pclass".to_string()];
+        let transformer = FeatureTransformer::new(&names).unwrap();
+        let actual = transformer.transform(&[fixture_input_data]).unwrap();
+        assert_eq!(actual.schema().names(), names);
+        assert_eq!(actual.row(0), vec![7.25, 3.0]);
+    }
+
+    #[rstest]
+    #[case("Braund, Mr. Owen Harris", 0.0)]
+    #[case("Cumings, Mrs. John Bradley (Mr. Nobody)", 1.0)]
+    #[case("Mayne, Mlle. Berthe Antonine", 2.0)]
+    #[case("Lahtinen, Rev. William", 5.0)]
+    fn test_FeatureTransformer_transform_title(
+        mut fixture_input_data: InputData,
+        #[case] name: &str,
+        #[case] expected: f64,
+    ) {
+        fixture_input_data.name = Some(PassengerName::parse(name));
+        let transformer = FeatureTransformer::new(&["title".to_string()]).unwrap();
+        let actual = transformer.transform(&[fixture_input_data]).unwrap();
+        assert_eq!(actual.row(0), vec![expected]);
+        assert!(actual.schema().fields()[0].categorical);
+    }
+
+    #[rstest]
+    fn test_FeatureTransformer_save_load() {
+        let path = temp_path("feature", "features.json");
+        let path = path.to_str().unwrap();
+        let names = vec!["fare".to_string(), "pclass".to_string()];
+        FeatureTransformer::new(&names).unwrap().save(path).unwrap();
+
+        let mut transformer = FeatureTransformer::new(&names).unwrap();
+        transformer.load(path).unwrap();
+        let mut transformer = FeatureTransformer::new(&["fare".to_string()]).unwrap();
+        let actual = transformer.load(path).err().unwrap();
+        assert!(format!("{:#}", actual).ends_with(
+            "was fitted for features [\"fare\", \"pclass\"], but [\"fare\"] are selected"
+        ));
+    }
+
+    #[rstest]
+    fn test_FeatureTransformer_transform_imputed(fixture_input_data: InputData) {
+        let build = || {
+            let imputer = Imputer::new(
+                Column::Age,
+                ImputeConfig {
+                    strategy: ImputeStrategy::Median,
+                    group_by: vec![],
+                    indicator: true,
+                },
+            );
+            FeatureTransformer::new(&["age".to_string(), "fare".to_string()])
+                .unwrap()
+                .with_imputer(imputer)
+        };
+        let mut transformer = build();
+        let missing = InputData {
+            age: None,
+            fare: None,
+            ..fixture_input_data.clone()
+        };
+        let train = vec![
+            fixture_input_data.clone(),
+            InputData {
+                age: Some(30.0),
+                ..fixture_input_data
+            },
+            missing.clone(),
+        ];
+        transformer.fit(&train).unwrap();
+        let actual = transformer
+            .transform(std::slice::from_ref(&missing))
+            .unwrap();
+        assert_eq!(actual.schema().names(), vec!["age", "fare", "age_missing"]);
+        assert_eq!(actual.schema().fields()[2].dtype, FeatureType::Binary);
+        assert_eq!(actual.row(0)[0], 26.0);
+        assert!(actual.row(0)[1].is_nan());
+        assert_eq!(actual.row(0)[2], 1.0);
+        assert_eq!(actual.missing_features(), vec!["fare"]);
+
+        let path = temp_path("feature", "imputed.json");
+        let path = path.to_str().unwrap();
+        transformer.save(path).unwrap();
+        let mut loaded = build();
+        loaded.load(path).unwrap();
+        assert_eq!(loaded.transform(&[missing]).unwrap().row(0)[0], 26.0);
+    }
+
+    #[rstest]
+    #[case(Some(1), Some(2), Some(4.0))]
+    #[case(None, Some(2), None)]
+    #[case(Some(1), None, None)]
+    fn test_FeatureTransformer_transform_family_size(
+        fixture_input_data: InputData,
+        #[case] sibsp: Option<i32>,
+        #[case] parch: Option<i32>,
+        #[case] expected: Option<f64>,
+    ) {
+        let transformer = FeatureTransformer::new(&["family_size".to_string()]).unwrap();
+        let input_data = InputData {
+            sibsp,
+            parch,
+            ..fixture_input_data
+        };
+        let actual = transformer.transform(&[input_data]).unwrap();
+        assert_eq!(Some(actual.row(0)[0]).filter(|v| !v.is_nan()), expected);
+    }
+
+    #[rstest]
+    fn test_FeatureTransformer_new_unknown() {
+        let actual = FeatureTransformer::new(&["deck".to_string()]);
+        assert_eq!(actual.err().unwrap().to_string(), "unknown feature: deck");
+    }
+}
@@ -0,0 +1,74 @@
+use anyhow::Result;
+
+use super::{AbstractFeatureTransformer, FeatureField, FeatureMatrix, FeatureSchema, MISSING};
+use crate::loader::InputData;
+
+/// `deck`, the deck letter as 0 for `A`, 1 for `B` and so on, missing without a cabin, and
+/// `cabin_count`, the number of cabins.
+#[derive(Debug, Default)]
+pub struct CabinTransformer;
+
+impl AbstractFeatureTransformer for CabinTransformer {
+    fn fit(&mut self, _input_data: &[InputData]) -> Result<()> {
+        Ok(())
+    }
+
+    fn transform(&self, input_data: &[InputData]) -> Result<FeatureMatrix> {
+        let schema = FeatureSchema::new(vec![
+            FeatureField::categorical("deck"),
+            FeatureField::integer("cabin_count"),
+        ])?;
+        let mut matrix = FeatureMatrix::with_capacity(schema, input_data.len());
+        for x in input_data.iter() {
+            let deck = x
+                .cabin
+                .as_ref()
+                .and_then(|cabin| cabin.deck())
+                .map_or(MISSING, |deck| f64::from(deck as u8 - b'A'));
+            let count = x.cabin.as_ref().map_or(0, |cabin| cabin.count());
+            matrix.push_row(&[deck, count as f64]);
+        }
+        Ok(matrix)
+    }
+
+    fn state(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::Value::Null)
+    }
+
+    fn set_state(&mut self, _state: serde_json::Value) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    use crate::loader::Cabin;
+    use crate::test_utils;
+
+    #[rstest]
+    #[case(Some("C23 C25 C27"), vec![Some(2.0), Some(3.0)])]
+    #[case(Some("F G73"), vec![Some(6.0), Some(1.0)])]
+    #[case(None, vec![None, Some(0.0)])]
+    fn test_CabinTransformer_transform(
+        #[case] cabin: Option<&str>,
+        #[case] expected: Vec<Option<f64>>,
+    ) {
+        let input_data = InputData {
+            cabin: cabin.map(Cabin::parse),
+            ..test_utils::input_data(1)
+        };
+        let actual = CabinTransformer.transform(&[input_data]).unwrap();
+        assert_eq!(actual.schema().names(), vec!["deck", "cabin_count"]);
+        let features = actual
+            .row(0)
+            .iter()
+            .map(|v| (!v.is_nan()).then_some(*v))
+            .collect::<Vec<_>>();
+        assert_eq!(features, expected);
+    }
+}
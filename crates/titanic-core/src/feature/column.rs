@@ -0,0 +1,79 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::loader::InputData;
+
+/// A column of the input data that features, encoders and imputers are built from, and that
+/// can be profiled or grouped by.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Column {
+    Pclass,
+    Sex,
+    Age,
+    SibSp,
+    Parch,
+    Fare,
+    Embarked,
+    Title,
+}
+
+pub const NUMERIC_COLUMNS: [Column; 4] = [Column::Age, Column::Fare, Column::SibSp, Column::Parch];
+pub const CATEGORICAL_COLUMNS: [Column; 4] =
+    [Column::Sex, Column::Embarked, Column::Pclass, Column::Title];
+
+impl Column {
+    pub(crate) fn numeric(&self, input_data: &InputData) -> Option<f64> {
+        match self {
+            Column::Pclass => input_data.pclass.map(|v| v as i32 as f64),
+            Column::Age => input_data.age,
+            Column::SibSp => input_data.sibsp.map(f64::from),
+            Column::Parch => input_data.parch.map(f64::from),
+            Column::Fare => input_data.fare,
+            Column::Sex | Column::Embarked | Column::Title => None,
+        }
+    }
+
+    /// The value as a group key; `None` if it is missing.
+    pub(crate) fn key(&self, input_data: &InputData) -> Option<String> {
+        match self {
+            Column::Pclass => input_data.pclass.map(|v| (v as i32).to_string()),
+            Column::Sex => input_data.sex.map(|v| format!("{:?}", v).to_lowercase()),
+            Column::Age | Column::SibSp | Column::Parch | Column::Fare => {
+                self.numeric(input_data).map(|v| v.to_string())
+            }
+            Column::Embarked => input_data.embarked.map(|v| format!("{:?}", v)),
+            Column::Title => input_data
+                .name
+                .as_ref()
+                .and_then(|name| name.title)
+                .map(|title| title.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", format!("{:?}", self).to_lowercase())
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "pclass" => Ok(Column::Pclass),
+            "sex" => Ok(Column::Sex),
+            "age" => Ok(Column::Age),
+            "sibsp" => Ok(Column::SibSp),
+            "parch" => Ok(Column::Parch),
+            "fare" => Ok(Column::Fare),
+            "embarked" => Ok(Column::Embarked),
+            "title" => Ok(Column::Title),
+            _ => Err(format!("unknown column: {}", s)),
+        }
+    }
+}
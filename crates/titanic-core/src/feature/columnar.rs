@@ -0,0 +1,129 @@
+use std::fs::{self, File};
+use std::path::Path;
+use std::sync::Arc;
+
+use ::arrow::array::{ArrayRef, Float64Array};
+use ::arrow::datatypes::{DataType, Field, Schema};
+use ::arrow::ipc::writer::FileWriter;
+use ::arrow::record_batch::RecordBatch;
+use anyhow::{bail, Result};
+use parquet::arrow::ArrowWriter;
+
+use super::FeatureMatrix;
+
+/// One `Float64` column per feature of the schema of `features`.
+pub fn features_to_record_batch(features: &FeatureMatrix) -> Result<RecordBatch> {
+    if features.schema().is_empty() {
+        bail!("no features to convert");
+    }
+    let schema = Schema::new(
+        features
+            .schema()
+            .names()
+            .into_iter()
+            .map(|name| Field::new(name, DataType::Float64, false))
+            .collect::<Vec<_>>(),
+    );
+    let columns = (0..features.n_cols())
+        .map(|j| Arc::new(Float64Array::from_iter_values(features.column(j))) as ArrayRef)
+        .collect();
+    Ok(RecordBatch::try_new(Arc::new(schema), columns)?)
+}
+
+/// Writes features to Parquet (`*.parquet`) or Arrow IPC (`*.arrow`, `*.ipc`, `*.feather`).
+pub fn save_features<P: AsRef<Path>>(path: P, features: &FeatureMatrix) -> Result<()> {
+    let path = path.as_ref();
+    let batch = features_to_record_batch(features)?;
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase());
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    match extension.as_deref() {
+        Some("parquet") => {
+            let mut writer = ArrowWriter::try_new(File::create(path)?, batch.schema(), None)?;
+            writer.write(&batch)?;
+            writer.close()?;
+        }
+        Some("arrow" | "ipc" | "feather") => {
+            let mut writer = FileWriter::try_new(File::create(path)?, &batch.schema())?;
+            writer.write(&batch)?;
+            writer.finish()?;
+        }
+        _ => bail!(
+            "cannot tell the format of {}; use a .parquet or .arrow extension",
+            path.display()
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use ::arrow::array::AsArray;
+    use ::arrow::datatypes::Float64Type;
+    use ::arrow::ipc::reader::FileReader;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use rstest::*;
+
+    use crate::feature::{FeatureField, FeatureSchema};
+    use crate::test_utils::temp_path;
+
+    fn features() -> FeatureMatrix {
+        let schema = FeatureSchema::new(vec![
+            FeatureField::float("fare"),
+            FeatureField::integer("pclass"),
+        ])
+        .unwrap();
+        let mut features = FeatureMatrix::new(schema);
+        features.push_row(&[7.25, 3.0]);
+        features.push_row(&[71.2833, 1.0]);
+        features
+    }
+
+    #[rstest]
+    #[case("features.parquet")]
+    #[case("features.arrow")]
+    fn test_save_features(#[case] name: &str) {
+        let path = temp_path("feature-columnar", name);
+        save_features(&path, &features()).unwrap();
+
+        let file = File::open(&path).unwrap();
+        let batch = if name.ends_with(".parquet") {
+            ParquetRecordBatchReaderBuilder::try_new(file)
+                .unwrap()
+                .build()
+                .unwrap()
+                .next()
+                .unwrap()
+                .unwrap()
+        } else {
+            FileReader::try_new(file, None)
+                .unwrap()
+                .next()
+                .unwrap()
+                .unwrap()
+        };
+        let names = batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["fare", "pclass"]);
+        let fare = batch.column(0).as_primitive::<Float64Type>();
+        assert_eq!(fare.values().to_vec(), vec![7.25, 71.2833]);
+    }
+
+    #[rstest]
+    fn test_features_to_record_batch_without_features() {
+        let actual = features_to_record_batch(&FeatureMatrix::empty(2))
+            .err()
+            .unwrap();
+        assert_eq!(actual.to_string(), "no features to convert");
+    }
+}
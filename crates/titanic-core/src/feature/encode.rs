@@ -0,0 +1,582 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, bail, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    AbstractFeatureTransformer, Column, FeatureField, FeatureMatrix, FeatureSchema, MISSING,
+};
+use crate::kfold::{AbstractKFold, KFold};
+use crate::loader::InputData;
+
+/// An encoder block of a [`super::FeaturePipeline`], e.g. `[features.encoders.title_onehot]`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum EncoderConfig {
+    OneHot {
+        column: Column,
+        #[serde(default = "default_min_count")]
+        min_count: usize,
+    },
+    Ordinal {
+        column: Column,
+        mapping: BTreeMap<String, f64>,
+    },
+    Count {
+        column: Column,
+        #[serde(default)]
+        normalize: bool,
+    },
+    Target {
+        column: Column,
+        #[serde(default = "default_smoothing")]
+        smoothing: f64,
+        #[serde(default = "default_n_splits")]
+        n_splits: usize,
+        #[serde(default)]
+        seed: u64,
+    },
+}
+
+fn default_min_count() -> usize {
+    1
+}
+
+fn default_smoothing() -> f64 {
+    10.0
+}
+
+fn default_n_splits() -> usize {
+    5
+}
+
+impl EncoderConfig {
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            EncoderConfig::Target { n_splits, .. } if *n_splits < 2 => {
+                bail!("target encoding needs at least 2 splits, got {}", n_splits)
+            }
+            EncoderConfig::Target { smoothing, .. } if *smoothing < 0.0 => {
+                bail!("smoothing must not be negative, got {}", smoothing)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn build(&self) -> Box<dyn AbstractFeatureTransformer> {
+        match self.clone() {
+            EncoderConfig::OneHot { column, min_count } => {
+                Box::new(OneHotEncoder::new(column, min_count))
+            }
+            EncoderConfig::Ordinal { column, mapping } => {
+                Box::new(OrdinalEncoder::new(column, mapping))
+            }
+            EncoderConfig::Count { column, normalize } => {
+                Box::new(CountEncoder::new(column, normalize))
+            }
+            EncoderConfig::Target {
+                column,
+                smoothing,
+                n_splits,
+                seed,
+            } => Box::new(TargetEncoder::new(column, smoothing, n_splits, seed)),
+        }
+    }
+}
+
+trait Encoder: Serialize + DeserializeOwned {
+    fn column(&self) -> Column;
+}
+
+fn encoder_state<E: Encoder>(encoder: &E) -> Result<serde_json::Value> {
+    Ok(serde_json::to_value(encoder)?)
+}
+
+/// Replaces `encoder` with the one in `state` if it encodes the same column.
+fn set_encoder_state<E: Encoder>(encoder: &mut E, state: serde_json::Value) -> Result<()> {
+    let loaded: E = serde_json::from_value(state)?;
+    if loaded.column() != encoder.column() {
+        bail!(
+            "the state is of an encoder of {}, not {}",
+            loaded.column(),
+            encoder.column()
+        );
+    }
+    *encoder = loaded;
+    Ok(())
+}
+
+/// A matrix of the single feature `field`.
+fn single_feature(field: FeatureField, values: impl Iterator<Item = f64>) -> Result<FeatureMatrix> {
+    let mut matrix = FeatureMatrix::new(FeatureSchema::new(vec![field])?);
+    for value in values {
+        matrix.push_row(&[value]);
+    }
+    Ok(matrix)
+}
+
+/// One column per level seen at least `min_count` times in fitting, named like `title_mr`,
+/// and `<column>_other` for the rarer and unseen levels; missing values are all zeros.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OneHotEncoder {
+    column: Column,
+    min_count: usize,
+    levels: Vec<String>,
+}
+
+impl OneHotEncoder {
+    pub fn new(column: Column, min_count: usize) -> Self {
+        Self {
+            column,
+            min_count,
+            levels: vec![],
+        }
+    }
+
+    fn names(&self) -> Vec<String> {
+        let mut names = self
+            .levels
+            .iter()
+            .map(|level| format!("{}_{}", self.column, level.to_lowercase()))
+            .collect::<Vec<_>>();
+        names.push(format!("{}_other", self.column));
+        names
+    }
+}
+
+impl Encoder for OneHotEncoder {
+    fn column(&self) -> Column {
+        self.column
+    }
+}
+
+impl AbstractFeatureTransformer for OneHotEncoder {
+    fn fit(&mut self, input_data: &[InputData]) -> Result<()> {
+        let mut counts = BTreeMap::new();
+        for key in input_data.iter().filter_map(|x| self.column.key(x)) {
+            *counts.entry(key).or_insert(0) += 1;
+        }
+        self.levels = counts
+            .into_iter()
+            .filter(|(_, count)| *count >= self.min_count)
+            .map(|(level, _)| level)
+            .collect();
+        Ok(())
+    }
+
+    fn transform(&self, input_data: &[InputData]) -> Result<FeatureMatrix> {
+        let schema =
+            FeatureSchema::new(self.names().into_iter().map(FeatureField::binary).collect())?;
+        let mut matrix = FeatureMatrix::with_capacity(schema, input_data.len());
+        let mut features = vec![0.0; matrix.n_cols()];
+        for x in input_data.iter() {
+            features.fill(0.0);
+            if let Some(key) = self.column.key(x) {
+                let i = self
+                    .levels
+                    .iter()
+                    .position(|level| *level == key)
+                    .unwrap_or(self.levels.len());
+                features[i] = 1.0;
+            }
+            matrix.push_row(&features);
+        }
+        Ok(matrix)
+    }
+
+    fn state(&self) -> Result<serde_json::Value> {
+        encoder_state(self)
+    }
+
+    fn set_state(&mut self, state: serde_json::Value) -> Result<()> {
+        set_encoder_state(self, state)
+    }
+}
+
+/// `<column>_ordinal` from an explicit level to value mapping; other levels and missing values
+/// are missing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrdinalEncoder {
+    column: Column,
+    mapping: BTreeMap<String, f64>,
+}
+
+impl OrdinalEncoder {
+    pub fn new(column: Column, mapping: BTreeMap<String, f64>) -> Self {
+        Self { column, mapping }
+    }
+}
+
+impl Encoder for OrdinalEncoder {
+    fn column(&self) -> Column {
+        self.column
+    }
+}
+
+impl AbstractFeatureTransformer for OrdinalEncoder {
+    fn fit(&mut self, _input_data: &[InputData]) -> Result<()> {
+        Ok(())
+    }
+
+    fn transform(&self, input_data: &[InputData]) -> Result<FeatureMatrix> {
+        single_feature(
+            FeatureField::float(format!("{}_ordinal", self.column)),
+            input_data.iter().map(|x| {
+                self.column
+                    .key(x)
+                    .and_then(|key| self.mapping.get(&key).copied())
+                    .unwrap_or(MISSING)
+            }),
+        )
+    }
+
+    fn state(&self) -> Result<serde_json::Value> {
+        encoder_state(self)
+    }
+
+    fn set_state(&mut self, state: serde_json::Value) -> Result<()> {
+        set_encoder_state(self, state)
+    }
+}
+
+/// `<column>_count`, the number of rows with the level in fitting, or `<column>_frequency`,
+/// their share, if `normalize`; unseen levels and missing values are 0.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CountEncoder {
+    column: Column,
+    normalize: bool,
+    counts: BTreeMap<String, usize>,
+    n_rows: usize,
+}
+
+impl CountEncoder {
+    pub fn new(column: Column, normalize: bool) -> Self {
+        Self {
+            column,
+            normalize,
+            counts: BTreeMap::new(),
+            n_rows: 0,
+        }
+    }
+}
+
+impl Encoder for CountEncoder {
+    fn column(&self) -> Column {
+        self.column
+    }
+}
+
+impl AbstractFeatureTransformer for CountEncoder {
+    fn fit(&mut self, input_data: &[InputData]) -> Result<()> {
+        self.counts.clear();
+        for key in input_data.iter().filter_map(|x| self.column.key(x)) {
+            *self.counts.entry(key).or_insert(0) += 1;
+        }
+        self.n_rows = input_data.len();
+        Ok(())
+    }
+
+    fn transform(&self, input_data: &[InputData]) -> Result<FeatureMatrix> {
+        let field = if self.normalize {
+            FeatureField::float(format!("{}_frequency", self.column))
+        } else {
+            FeatureField::integer(format!("{}_count", self.column))
+        };
+        single_feature(
+            field,
+            input_data.iter().map(|x| {
+                let count = self
+                    .column
+                    .key(x)
+                    .and_then(|key| self.counts.get(&key).copied())
+                    .unwrap_or(0) as f64;
+                if self.normalize && self.n_rows > 0 {
+                    count / self.n_rows as f64
+                } else {
+                    count
+                }
+            }),
+        )
+    }
+
+    fn state(&self) -> Result<serde_json::Value> {
+        encoder_state(self)
+    }
+
+    fn set_state(&mut self, state: serde_json::Value) -> Result<()> {
+        set_encoder_state(self, state)
+    }
+}
+
+/// `<column>_target`, the survival rate of the level shrunk towards the overall rate:
+/// `(survivors + smoothing * prior) / (rows + smoothing)`; unseen levels and missing values
+/// get the overall rate.
+///
+/// The rows `fit` is called with are encoded out-of-fold, from the other `n_splits - 1` parts
+/// of them, so their own labels never reach their features. Rows are matched by passenger id.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetEncoder {
+    column: Column,
+    smoothing: f64,
+    n_splits: usize,
+    seed: u64,
+    prior: f64,
+    rates: BTreeMap<String, f64>,
+    out_of_fold: BTreeMap<u32, f64>,
+}
+
+impl TargetEncoder {
+    pub fn new(column: Column, smoothing: f64, n_splits: usize, seed: u64) -> Self {
+        Self {
+            column,
+            smoothing,
+            n_splits,
+            seed,
+            prior: 0.0,
+            rates: BTreeMap::new(),
+            out_of_fold: BTreeMap::new(),
+        }
+    }
+
+    /// The overall rate and the smoothed rate of each level in `rows`.
+    fn rates(&self, rows: &[(Option<String>, f64)]) -> (f64, BTreeMap<String, f64>) {
+        let prior = rows.iter().map(|(_, y)| y).sum::<f64>() / rows.len().max(1) as f64;
+        let mut stats: BTreeMap<String, (f64, f64)> = BTreeMap::new();
+        for (key, y) in rows.iter() {
+            if let Some(key) = key {
+                let (sum, count) = stats.entry(key.clone()).or_default();
+                *sum += y;
+                *count += 1.0;
+            }
+        }
+        let rates = stats
+            .into_iter()
+            .map(|(key, (sum, count))| {
+                (
+                    key,
+                    (sum + self.smoothing * prior) / (count + self.smoothing),
+                )
+            })
+            .collect();
+        (prior, rates)
+    }
+}
+
+impl Encoder for TargetEncoder {
+    fn column(&self) -> Column {
+        self.column
+    }
+}
+
+impl AbstractFeatureTransformer for TargetEncoder {
+    fn fit(&mut self, input_data: &[InputData]) -> Result<()> {
+        if self.n_splits < 2 {
+            bail!(
+                "target encoding needs at least 2 splits, got {}",
+                self.n_splits
+            );
+        }
+        let rows = input_data
+            .iter()
+            .map(|x| {
+                let y = x.survived.ok_or_else(|| {
+                    anyhow!(
+                        "cannot target encode {}: passenger {} has no label",
+                        self.column,
+                        x.passenger_id
+                    )
+                })?;
+                Ok((self.column.key(x), f64::from(y)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        (self.prior, self.rates) = self.rates(&rows);
+
+        self.out_of_fold.clear();
+        let folds = KFold::new(self.n_splits, true, Some(self.seed)).split(input_data, vec![]);
+        for fold in folds.iter() {
+            let rest = (0..rows.len())
+                .filter(|i| !fold.contains(i))
+                .map(|i| rows[i].clone())
+                .collect::<Vec<_>>();
+            let (prior, rates) = self.rates(&rest);
+            for &i in fold.iter() {
+                let value = rows[i]
+                    .0
+                    .as_ref()
+                    .and_then(|key| rates.get(key).copied())
+                    .unwrap_or(prior);
+                self.out_of_fold.insert(input_data[i].passenger_id, value);
+            }
+        }
+        Ok(())
+    }
+
+    fn transform(&self, input_data: &[InputData]) -> Result<FeatureMatrix> {
+        single_feature(
+            FeatureField::float(format!("{}_target", self.column)),
+            input_data.iter().map(|x| {
+                self.out_of_fold
+                    .get(&x.passenger_id)
+                    .copied()
+                    .unwrap_or_else(|| {
+                        self.column
+                            .key(x)
+                            .and_then(|key| self.rates.get(&key).copied())
+                            .unwrap_or(self.prior)
+                    })
+            }),
+        )
+    }
+
+    fn state(&self) -> Result<serde_json::Value> {
+        encoder_state(self)
+    }
+
+    fn set_state(&mut self, state: serde_json::Value) -> Result<()> {
+        set_encoder_state(self, state)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    use crate::loader::{Embarked, Sex};
+    use crate::test_utils::{self, temp_path};
+
+    fn input_data(passenger_id: u32, embarked: Option<Embarked>, survived: u32) -> InputData {
+        InputData {
+            survived: Some(survived),
+            sex: Some(Sex::Male),
+            embarked,
+            ..test_utils::input_data(passenger_id)
+        }
+    }
+
+    fn train() -> Vec<InputData> {
+        vec![
+            input_data(1, Some(Embarked::S), 0),
+            input_data(2, Some(Embarked::S), 1),
+            input_data(3, Some(Embarked::S), 0),
+            input_data(4, Some(Embarked::C), 1),
+            input_data(5, Some(Embarked::C), 1),
+            input_data(6, Some(Embarked::Q), 0),
+            input_data(7, None, 1),
+            input_data(8, Some(Embarked::S), 1),
+        ]
+    }
+
+    #[rstest]
+    fn test_OneHotEncoder_transform() {
+        let mut encoder = OneHotEncoder::new(Column::Embarked, 2);
+        encoder.fit(&train()).unwrap();
+        let actual = encoder.transform(&train()[4..8]).unwrap();
+        assert_eq!(
+            actual.schema().names(),
+            vec!["embarked_c", "embarked_s", "embarked_other"]
+        );
+        let features = actual.rows().map(<[f64]>::to_vec).collect::<Vec<_>>();
+        assert_eq!(
+            features,
+            vec![
+                vec![1.0, 0.0, 0.0],
+                vec![0.0, 0.0, 1.0],
+                vec![0.0, 0.0, 0.0],
+                vec![0.0, 1.0, 0.0],
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_OrdinalEncoder_transform() {
+        let mapping = BTreeMap::from([("S".to_string(), 0.0), ("C".to_string(), 1.0)]);
+        let encoder = OrdinalEncoder::new(Column::Embarked, mapping);
+        let actual = encoder.transform(&train()[4..7]).unwrap();
+        assert_eq!(actual.schema().names(), vec!["embarked_ordinal"]);
+        let features = actual
+            .column(0)
+            .map(|v| (!v.is_nan()).then_some(v))
+            .collect::<Vec<_>>();
+        assert_eq!(features, vec![Some(1.0), None, None]);
+    }
+
+    #[rstest]
+    #[case(false, "embarked_count", vec![2.0, 1.0, 0.0, 4.0])]
+    #[case(true, "embarked_frequency", vec![0.25, 0.125, 0.0, 0.5])]
+    fn test_CountEncoder_transform(
+        #[case] normalize: bool,
+        #[case] name: &str,
+        #[case] expected: Vec<f64>,
+    ) {
+        let mut encoder = CountEncoder::new(Column::Embarked, normalize);
+        encoder.fit(&train()).unwrap();
+        let actual = encoder.transform(&train()[4..8]).unwrap();
+        assert_eq!(actual.schema().names(), vec![name]);
+        let features = actual.column(0).collect::<Vec<_>>();
+        assert_eq!(features, expected);
+    }
+
+    #[rstest]
+    fn test_TargetEncoder_transform() {
+        let mut encoder = TargetEncoder::new(Column::Embarked, 2.0, 2, 42);
+        let train = train();
+        encoder.fit(&train).unwrap();
+
+        // Unseen rows use every training label: S has 2 of 4 survivors and the prior is 5 / 8.
+        let test = vec![
+            input_data(10, Some(Embarked::S), 0),
+            input_data(11, None, 0),
+        ];
+        let actual = encoder.transform(&test).unwrap();
+        assert_eq!(actual.schema().names(), vec!["embarked_target"]);
+        assert_eq!(actual.row(0)[0], (2.0 + 2.0 * 0.625) / (4.0 + 2.0));
+        assert_eq!(actual.row(1)[0], 0.625);
+
+        // Training rows are encoded without their own label.
+        let actual = encoder.transform(&train).unwrap();
+        let full = encoder.transform(&test[..1]).unwrap().row(0)[0];
+        for (x, value) in train.iter().zip(actual.column(0)) {
+            assert_eq!(value, encoder.out_of_fold[&x.passenger_id]);
+        }
+        assert!(actual.column(0).any(|value| value != full));
+
+        let path = temp_path("encode", "target.json");
+        let path = path.to_str().unwrap();
+        encoder.save(path).unwrap();
+        let mut loaded = TargetEncoder::new(Column::Embarked, 0.0, 2, 0);
+        loaded.load(path).unwrap();
+        assert_eq!(loaded.smoothing, 2.0);
+        let actual = loaded.transform(&test).unwrap();
+        assert!((actual.row(0)[0] - full).abs() < 1e-12);
+    }
+
+    #[rstest]
+    fn test_TargetEncoder_fit_without_labels() {
+        let mut encoder = TargetEncoder::new(Column::Embarked, 2.0, 2, 42);
+        let data = vec![InputData {
+            survived: None,
+            ..input_data(1, Some(Embarked::S), 0)
+        }];
+        let actual = encoder.fit(&data).err().unwrap();
+        assert_eq!(
+            actual.to_string(),
+            "cannot target encode embarked: passenger 1 has no label"
+        );
+    }
+
+    #[rstest]
+    fn test_OneHotEncoder_load_other_column() {
+        let path = temp_path("encode", "onehot.json");
+        let path = path.to_str().unwrap();
+        OneHotEncoder::new(Column::Sex, 1).save(path).unwrap();
+        let mut encoder = OneHotEncoder::new(Column::Embarked, 1);
+        let actual = encoder.load(path).err().unwrap();
+        assert!(
+            format!("{:#}", actual).ends_with("the state is of an encoder of sex, not embarked")
+        );
+    }
+}
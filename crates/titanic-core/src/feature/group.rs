@@ -0,0 +1,511 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{AbstractFeatureTransformer, FeatureField, FeatureMatrix, FeatureSchema, MISSING};
+use crate::kfold::{AbstractKFold, KFold};
+use crate::loader::InputData;
+
+/// Travel-group features, counted over train and test together by
+/// [`AbstractFeatureTransformer::fit_unlabeled`]:
+///
+/// - `ticket_group_size`, `surname_group_size` and `cabin_group_size`, the number of passengers
+///   with the same ticket, surname or cabin; rows not seen in fitting count as a group of one
+/// - `fare_per_person`, the fare divided by the ticket group size, as fares are per ticket
+/// - `is_alone`, without siblings, spouses, parents or children aboard, and `ticket_alone`,
+///   the only passenger on the ticket
+///
+/// Features of missing tickets, surnames, cabins and fares are missing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GroupTransformer {
+    tickets: BTreeMap<String, usize>,
+    surnames: BTreeMap<String, usize>,
+    cabins: BTreeMap<String, usize>,
+}
+
+fn ticket_key(x: &InputData) -> Option<&str> {
+    x.ticket.as_ref().map(|ticket| ticket.full.as_str())
+}
+
+fn surname_key(x: &InputData) -> Option<&str> {
+    x.name.as_ref().and_then(|name| name.surname.as_deref())
+}
+
+fn cabin_key(x: &InputData) -> Option<&str> {
+    x.cabin.as_ref().map(|cabin| cabin.full.as_str())
+}
+
+fn count(input_data: &[InputData], key: fn(&InputData) -> Option<&str>) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for key in input_data.iter().filter_map(key) {
+        *counts.entry(key.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// The size of the group of `key`, at least 1, or missing without a key.
+fn group_size(counts: &BTreeMap<String, usize>, key: Option<&str>) -> f64 {
+    key.map_or(MISSING, |key| counts.get(key).copied().unwrap_or(1) as f64)
+}
+
+impl GroupTransformer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl AbstractFeatureTransformer for GroupTransformer {
+    fn fit(&mut self, _input_data: &[InputData]) -> Result<()> {
+        Ok(())
+    }
+
+    fn fit_unlabeled(&mut self, input_data: &[InputData]) -> Result<()> {
+        self.tickets = count(input_data, ticket_key);
+        self.surnames = count(input_data, surname_key);
+        self.cabins = count(input_data, cabin_key);
+        Ok(())
+    }
+
+    fn transform(&self, input_data: &[InputData]) -> Result<FeatureMatrix> {
+        let schema = FeatureSchema::new(vec![
+            FeatureField::integer("ticket_group_size"),
+            FeatureField::integer("surname_group_size"),
+            FeatureField::integer("cabin_group_size"),
+            FeatureField::float("fare_per_person"),
+            FeatureField::binary("is_alone"),
+            FeatureField::binary("ticket_alone"),
+        ])?;
+        let mut matrix = FeatureMatrix::with_capacity(schema, input_data.len());
+        for x in input_data.iter() {
+            let ticket_group_size = group_size(&self.tickets, ticket_key(x));
+            let is_alone = match (x.sibsp, x.parch) {
+                (Some(sibsp), Some(parch)) => f64::from(sibsp + parch == 0),
+                _ => MISSING,
+            };
+            let ticket_alone = if ticket_group_size.is_nan() {
+                MISSING
+            } else {
+                f64::from(ticket_group_size == 1.0)
+            };
+            matrix.push_row(&[
+                ticket_group_size,
+                group_size(&self.surnames, surname_key(x)),
+                group_size(&self.cabins, cabin_key(x)),
+                x.fare.map_or(MISSING, |fare| fare / ticket_group_size),
+                is_alone,
+                ticket_alone,
+            ]);
+        }
+        Ok(matrix)
+    }
+
+    fn state(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::to_value(self)?)
+    }
+
+    fn set_state(&mut self, state: serde_json::Value) -> Result<()> {
+        *self = serde_json::from_value(state)?;
+        Ok(())
+    }
+}
+
+/// `[features.group_survival]`, for the `group_survival` block.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GroupSurvivalConfig {
+    /// Weight of the overall survival rate the group rate is shrunk towards.
+    pub smoothing: f64,
+    /// The value without labelled peers; missing if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unknown: Option<f64>,
+    /// Parts the fitted rows are split into to compute their rates out-of-fold.
+    pub n_splits: usize,
+    pub seed: u64,
+}
+
+impl Default for GroupSurvivalConfig {
+    fn default() -> Self {
+        Self {
+            smoothing: 1.0,
+            unknown: None,
+            n_splits: 5,
+            seed: 0,
+        }
+    }
+}
+
+/// Survivors and rows of a group.
+type GroupLabels = BTreeMap<String, (f64, f64)>;
+
+/// The overall survival rate of labelled rows and the survivors and rows of their ticket and
+/// surname groups.
+fn group_labels<'a>(
+    rows: impl Iterator<Item = (&'a InputData, f64)>,
+) -> (f64, GroupLabels, GroupLabels) {
+    let (mut survivors, mut count) = (0.0, 0.0);
+    let mut tickets = GroupLabels::new();
+    let mut surnames = GroupLabels::new();
+    for (x, label) in rows {
+        survivors += label;
+        count += 1.0;
+        for (groups, key) in [
+            (&mut tickets, ticket_key(x)),
+            (&mut surnames, surname_key(x)),
+        ] {
+            if let Some(key) = key {
+                let (survivors, rows) = groups.entry(key.to_string()).or_default();
+                *survivors += label;
+                *rows += 1.0;
+            }
+        }
+    }
+    (survivors / f64::max(count, 1.0), tickets, surnames)
+}
+
+/// `ticket_survival` and `surname_survival`, the survival rate of the ticket or surname group,
+/// `(survivors + smoothing * prior) / (rows + smoothing)`.
+///
+/// Only the labels of the rows `fit` is called with, the training fold in `run_fold`, are used.
+/// Those rows are themselves encoded out-of-fold, from the other `n_splits - 1` parts of them,
+/// so no row's label reaches its own features; other rows use all of them. Rows are matched by
+/// passenger id, and rows without labelled peers get [`GroupSurvivalConfig::unknown`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GroupSurvivalTransformer {
+    config: GroupSurvivalConfig,
+    prior: f64,
+    tickets: GroupLabels,
+    surnames: GroupLabels,
+    /// The rates of the fitted rows by passenger id, `None` without labelled peers.
+    out_of_fold: BTreeMap<u32, [Option<f64>; 2]>,
+}
+
+impl GroupSurvivalTransformer {
+    pub fn new(config: GroupSurvivalConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// The smoothed rates of the ticket and surname groups of `x`, `None` for a group without
+    /// labelled rows.
+    fn rates(
+        &self,
+        prior: f64,
+        tickets: &GroupLabels,
+        surnames: &GroupLabels,
+        x: &InputData,
+    ) -> [Option<f64>; 2] {
+        let rate = |groups: &GroupLabels, key: Option<&str>| {
+            key.and_then(|key| groups.get(key))
+                .map(|(survivors, rows)| {
+                    (survivors + self.config.smoothing * prior) / (rows + self.config.smoothing)
+                })
+        };
+        [rate(tickets, ticket_key(x)), rate(surnames, surname_key(x))]
+    }
+}
+
+impl AbstractFeatureTransformer for GroupSurvivalTransformer {
+    fn fit(&mut self, input_data: &[InputData]) -> Result<()> {
+        if self.config.n_splits < 2 {
+            bail!(
+                "group survival rates need at least 2 splits, got {}",
+                self.config.n_splits
+            );
+        }
+        let labels = input_data
+            .iter()
+            .map(|x| {
+                let label = x.survived.ok_or_else(|| {
+                    anyhow!(
+                        "cannot compute group survival rates: passenger {} has no label",
+                        x.passenger_id
+                    )
+                })?;
+                Ok(f64::from(label))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        (self.prior, self.tickets, self.surnames) =
+            group_labels(input_data.iter().zip(labels.iter().copied()));
+
+        self.out_of_fold.clear();
+        let folds = KFold::new(self.config.n_splits, true, Some(self.config.seed))
+            .split(input_data, vec![]);
+        for fold in folds.iter() {
+            let (prior, tickets, surnames) = group_labels(
+                (0..input_data.len())
+                    .filter(|i| !fold.contains(i))
+                    .map(|i| (&input_data[i], labels[i])),
+            );
+            for &i in fold.iter() {
+                let x = &input_data[i];
+                let rates = self.rates(prior, &tickets, &surnames, x);
+                self.out_of_fold.insert(x.passenger_id, rates);
+            }
+        }
+        Ok(())
+    }
+
+    fn transform(&self, input_data: &[InputData]) -> Result<FeatureMatrix> {
+        let schema = FeatureSchema::new(vec![
+            FeatureField::float("ticket_survival"),
+            FeatureField::float("surname_survival"),
+        ])?;
+        let mut matrix = FeatureMatrix::with_capacity(schema, input_data.len());
+        for x in input_data.iter() {
+            let rates = self
+                .out_of_fold
+                .get(&x.passenger_id)
+                .copied()
+                .unwrap_or_else(|| self.rates(self.prior, &self.tickets, &self.surnames, x))
+                .map(|rate| rate.or(self.config.unknown).unwrap_or(MISSING));
+            matrix.push_row(&rates);
+        }
+        Ok(matrix)
+    }
+
+    fn state(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::to_value(self)?)
+    }
+
+    fn set_state(&mut self, state: serde_json::Value) -> Result<()> {
+        *self = serde_json::from_value(state)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    use crate::loader::{Cabin, PassengerName, Ticket};
+    use crate::test_utils;
+
+    fn input_data(
+        passenger_id: u32,
+        name: &str,
+        ticket: Option<&str>,
+        cabin: Option<&str>,
+        fare: Option<f64>,
+    ) -> InputData {
+        InputData {
+            name: Some(PassengerName::parse(name)),
+            sibsp: Some(0),
+            parch: Some(i32::from(passenger_id < 3)),
+            ticket: ticket.map(Ticket::parse),
+            fare,
+            cabin: cabin.map(Cabin::parse),
+            ..test_utils::input_data(passenger_id)
+        }
+    }
+
+    fn data() -> Vec<InputData> {
+        vec![
+            input_data(
+                1,
+                "Allison, Miss. Helen Loraine",
+                Some("113781"),
+                Some("C22 C26"),
+                Some(151.55),
+            ),
+            input_data(
+                2,
+                "Allison, Mr. Hudson Joshua Creighton",
+                Some("113781"),
+                Some("C22 C26"),
+                Some(151.55),
+            ),
+            input_data(
+                3,
+                "Braund, Mr. Owen Harris",
+                Some("A/5 21171"),
+                None,
+                Some(7.25),
+            ),
+            input_data(
+                4,
+                "Allison, Master. Hudson Trevor",
+                Some("113781"),
+                None,
+                None,
+            ),
+        ]
+    }
+
+    fn features(matrix: &FeatureMatrix, i: usize) -> Vec<Option<f64>> {
+        matrix
+            .row(i)
+            .iter()
+            .map(|v| (!v.is_nan()).then_some(*v))
+            .collect()
+    }
+
+    #[rstest]
+    fn test_GroupTransformer_transform() {
+        let mut transformer = GroupTransformer::new();
+        transformer.fit_unlabeled(&data()).unwrap();
+        let actual = transformer.transform(&data()).unwrap();
+        assert_eq!(
+            actual.schema().names(),
+            vec![
+                "ticket_group_size",
+                "surname_group_size",
+                "cabin_group_size",
+                "fare_per_person",
+                "is_alone",
+                "ticket_alone"
+            ]
+        );
+        assert_eq!(
+            features(&actual, 0),
+            vec![
+                Some(3.0),
+                Some(3.0),
+                Some(2.0),
+                Some(151.55 / 3.0),
+                Some(0.0),
+                Some(0.0)
+            ]
+        );
+        assert_eq!(
+            features(&actual, 2),
+            vec![Some(1.0), Some(1.0), None, Some(7.25), Some(1.0), Some(1.0)]
+        );
+        assert_eq!(
+            features(&actual, 3),
+            vec![Some(3.0), Some(3.0), None, None, Some(1.0), Some(0.0)]
+        );
+
+        let unseen = input_data(
+            5,
+            "Cumings, Mrs. John Bradley",
+            Some("PC 17599"),
+            None,
+            Some(71.28),
+        );
+        let actual = transformer.transform(&[unseen]).unwrap();
+        assert_eq!(
+            features(&actual, 0),
+            vec![
+                Some(1.0),
+                Some(1.0),
+                None,
+                Some(71.28),
+                Some(1.0),
+                Some(1.0)
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_GroupTransformer_set_state() {
+        let mut transformer = GroupTransformer::new();
+        transformer.fit_unlabeled(&data()).unwrap();
+        let mut loaded = GroupTransformer::new();
+        loaded.set_state(transformer.state().unwrap()).unwrap();
+        assert_eq!(loaded, transformer);
+    }
+
+    fn labelled(x: InputData, survived: u32) -> InputData {
+        InputData {
+            survived: Some(survived),
+            ..x
+        }
+    }
+
+    fn train_fold(first_survived: u32) -> Vec<InputData> {
+        let data = data();
+        vec![
+            labelled(data[0].clone(), first_survived),
+            labelled(data[1].clone(), 0),
+            labelled(data[2].clone(), 0),
+        ]
+    }
+
+    fn config() -> GroupSurvivalConfig {
+        GroupSurvivalConfig {
+            smoothing: 1.0,
+            unknown: None,
+            n_splits: 3,
+            seed: 0,
+        }
+    }
+
+    #[rstest]
+    fn test_GroupSurvivalTransformer_transform() {
+        let train_fold = train_fold(1);
+        let mut transformer = GroupSurvivalTransformer::new(config());
+        transformer.fit(&train_fold).unwrap();
+
+        // With a row per fold, each fitted row is encoded from the other two; Braund has no
+        // labelled peers.
+        let actual = transformer.transform(&train_fold).unwrap();
+        assert_eq!(
+            actual.schema().names(),
+            vec!["ticket_survival", "surname_survival"]
+        );
+        assert_eq!(features(&actual, 0), vec![Some(0.0); 2]);
+        assert_eq!(features(&actual, 1), vec![Some((1.0 + 0.5) / 2.0); 2]);
+        assert_eq!(features(&actual, 2), vec![None, None]);
+
+        // Other rows use every fitted row and never their own label.
+        let prior = 1.0 / 3.0;
+        for survived in [0, 1] {
+            let valid = labelled(data()[3].clone(), survived);
+            let actual = transformer.transform(&[valid]).unwrap();
+            assert_eq!(features(&actual, 0), vec![Some((1.0 + prior) / 3.0); 2]);
+        }
+
+        let mut transformer = GroupSurvivalTransformer::new(GroupSurvivalConfig {
+            unknown: Some(0.5),
+            ..config()
+        });
+        transformer.fit(&train_fold).unwrap();
+        let mut loaded = GroupSurvivalTransformer::new(config());
+        loaded.set_state(transformer.state().unwrap()).unwrap();
+        let actual = loaded.transform(&train_fold[2..]).unwrap();
+        assert_eq!(features(&actual, 0), vec![Some(0.5); 2]);
+    }
+
+    #[rstest]
+    fn test_GroupSurvivalTransformer_set_state_without_unknown() {
+        let train_fold = train_fold(1);
+        let mut transformer = GroupSurvivalTransformer::new(config());
+        transformer.fit(&train_fold).unwrap();
+        let state = serde_json::to_string(&transformer.state().unwrap()).unwrap();
+        let mut loaded = GroupSurvivalTransformer::new(config());
+        loaded
+            .set_state(serde_json::from_str(&state).unwrap())
+            .unwrap();
+        assert_eq!(loaded, transformer);
+        let actual = loaded.transform(&train_fold).unwrap();
+        assert_eq!(features(&actual, 2), vec![None, None]);
+    }
+
+    #[rstest]
+    fn test_GroupSurvivalTransformer_transform_own_label() {
+        let fitted = |survived| {
+            let train_fold = train_fold(survived);
+            let mut transformer = GroupSurvivalTransformer::new(config());
+            transformer.fit(&train_fold).unwrap();
+            features(&transformer.transform(&train_fold).unwrap(), 0)
+        };
+        assert_eq!(fitted(0), vec![Some(0.0); 2]);
+        assert_eq!(fitted(1), fitted(0));
+    }
+
+    #[rstest]
+    fn test_GroupSurvivalTransformer_fit_without_labels() {
+        let mut transformer = GroupSurvivalTransformer::new(GroupSurvivalConfig::default());
+        let actual = transformer.fit(&data()).err().unwrap();
+        assert_eq!(
+            actual.to_string(),
+            "cannot compute group survival rates: passenger 1 has no label"
+        );
+    }
+}
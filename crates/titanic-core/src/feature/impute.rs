@@ -0,0 +1,437 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{Column, CATEGORICAL_COLUMNS, NUMERIC_COLUMNS};
+use crate::loader::InputData;
+use crate::utils::quantile;
+
+/// Columns whose missing values can be imputed.
+pub const IMPUTABLE_COLUMNS: [Column; 2] = [Column::Age, Column::Fare];
+
+/// Ridge penalty of the regression imputer; only keeps the normal equations solvable.
+const RIDGE_PENALTY: f64 = 1e-3;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImputeStrategy {
+    Mean,
+    Median,
+    /// Median of the rows with the same `group_by` values, or the overall median for groups
+    /// not seen in fitting.
+    GroupedMedian,
+    /// Linear regression on the other columns, clipped to the range seen in fitting.
+    Regression,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImputeConfig {
+    pub strategy: ImputeStrategy,
+    /// Columns to group by for `grouped_median`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub group_by: Vec<Column>,
+    /// Adds a `<column>_missing` feature that is 1 where the value was missing.
+    #[serde(default)]
+    pub indicator: bool,
+}
+
+impl ImputeConfig {
+    pub fn validate(&self, column: Column) -> Result<()> {
+        if !IMPUTABLE_COLUMNS.contains(&column) {
+            bail!(
+                "{} cannot be imputed; imputable columns: {:?}",
+                column,
+                IMPUTABLE_COLUMNS.map(|c| c.to_string())
+            );
+        }
+        match self.strategy {
+            ImputeStrategy::GroupedMedian if self.group_by.is_empty() => {
+                bail!("grouped_median imputation of {} needs group_by", column)
+            }
+            ImputeStrategy::GroupedMedian if self.group_by.contains(&column) => {
+                bail!("{} cannot be imputed grouped by itself", column)
+            }
+            ImputeStrategy::GroupedMedian => {}
+            _ if !self.group_by.is_empty() => {
+                bail!("group_by is only used by grouped_median imputation")
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Fill values learned from the training data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FittedImputer {
+    Constant(f64),
+    Grouped {
+        medians: Vec<(Vec<Option<String>>, f64)>,
+        fallback: f64,
+    },
+    Regression(Regression),
+}
+
+/// Least squares on the other columns: numeric ones as is, with 0 for missing values, and
+/// categorical ones one-hot encoded over the levels seen in fitting.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Regression {
+    numeric: Vec<Column>,
+    levels: Vec<(Column, Vec<String>)>,
+    /// Intercept first, then one per design column.
+    coefficients: Vec<f64>,
+    min: f64,
+    max: f64,
+}
+
+impl Regression {
+    fn fit(target: Column, data: &[InputData]) -> Result<Self> {
+        let rows = data
+            .iter()
+            .filter_map(|x| target.numeric(x).map(|y| (x, y)))
+            .collect::<Vec<_>>();
+        let numeric = NUMERIC_COLUMNS
+            .into_iter()
+            .filter(|column| *column != target)
+            .collect::<Vec<_>>();
+        let levels = CATEGORICAL_COLUMNS
+            .into_iter()
+            .map(|column| {
+                let mut levels = rows
+                    .iter()
+                    .filter_map(|(x, _)| column.key(x))
+                    .collect::<Vec<_>>();
+                levels.sort();
+                levels.dedup();
+                (column, levels)
+            })
+            .collect::<Vec<_>>();
+        let mut regression = Self {
+            numeric,
+            levels,
+            coefficients: vec![],
+            min: rows.iter().map(|(_, y)| *y).fold(f64::INFINITY, f64::min),
+            max: rows
+                .iter()
+                .map(|(_, y)| *y)
+                .fold(f64::NEG_INFINITY, f64::max),
+        };
+
+        let n = 1
+            + regression.numeric.len()
+            + regression
+                .levels
+                .iter()
+                .map(|(_, l)| l.len())
+                .sum::<usize>();
+        let mut xtx = vec![vec![0.0; n]; n];
+        let mut xty = vec![0.0; n];
+        for (x, y) in rows.iter() {
+            let design = regression.design(x);
+            for i in 0..n {
+                xty[i] += design[i] * y;
+                for j in 0..n {
+                    xtx[i][j] += design[i] * design[j];
+                }
+            }
+        }
+        for (i, row) in xtx.iter_mut().enumerate().skip(1) {
+            row[i] += RIDGE_PENALTY;
+        }
+        regression.coefficients = solve(xtx, xty)
+            .ok_or_else(|| anyhow!("cannot fit the regression imputer of {}", target))?;
+        Ok(regression)
+    }
+
+    fn design(&self, input_data: &InputData) -> Vec<f64> {
+        let mut design = vec![1.0];
+        design.extend(
+            self.numeric
+                .iter()
+                .map(|column| column.numeric(input_data).unwrap_or(0.0)),
+        );
+        for (column, levels) in self.levels.iter() {
+            let key = column.key(input_data);
+            design.extend(
+                levels
+                    .iter()
+                    .map(|level| f64::from(key.as_ref() == Some(level))),
+            );
+        }
+        design
+    }
+
+    fn predict(&self, input_data: &InputData) -> f64 {
+        let prediction = self
+            .design(input_data)
+            .iter()
+            .zip(self.coefficients.iter())
+            .map(|(x, c)| x * c)
+            .sum::<f64>();
+        prediction.clamp(self.min, self.max)
+    }
+}
+
+/// Solves `a x = b` by Gaussian elimination with partial pivoting; `None` if `a` is singular.
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|i, j| a[*i][col].abs().total_cmp(&a[*j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let pivot_row = a[col].clone();
+        for row in col + 1..n {
+            let factor = a[row][col] / pivot_row[col];
+            for (value, pivot_value) in a[row].iter_mut().zip(pivot_row.iter()).skip(col) {
+                *value -= factor * pivot_value;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum = (row + 1..n).map(|k| a[row][k] * x[k]).sum::<f64>();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    Some(x)
+}
+
+/// Fills the missing values of one column.
+#[derive(Debug, Clone)]
+pub struct Imputer {
+    pub column: Column,
+    pub config: ImputeConfig,
+    fitted: Option<FittedImputer>,
+}
+
+impl Imputer {
+    pub fn new(column: Column, config: ImputeConfig) -> Self {
+        Self {
+            column,
+            config,
+            fitted: None,
+        }
+    }
+
+    /// Learns the fill values from the non-missing values of `input_data`.
+    pub fn fit(&mut self, input_data: &[InputData]) -> Result<()> {
+        let mut values = input_data
+            .iter()
+            .filter_map(|x| self.column.numeric(x))
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            bail!("cannot impute {}: every value is missing", self.column);
+        }
+        values.sort_by(f64::total_cmp);
+        let median = quantile(&values, 0.5).unwrap();
+        let fitted = match self.config.strategy {
+            ImputeStrategy::Mean => {
+                FittedImputer::Constant(values.iter().sum::<f64>() / values.len() as f64)
+            }
+            ImputeStrategy::Median => FittedImputer::Constant(median),
+            ImputeStrategy::GroupedMedian => {
+                let mut groups: BTreeMap<Vec<Option<String>>, Vec<f64>> = BTreeMap::new();
+                for x in input_data.iter() {
+                    if let Some(value) = self.column.numeric(x) {
+                        groups.entry(self.group_key(x)).or_default().push(value);
+                    }
+                }
+                let medians = groups
+                    .into_iter()
+                    .map(|(key, mut values)| {
+                        values.sort_by(f64::total_cmp);
+                        (key, quantile(&values, 0.5).unwrap())
+                    })
+                    .collect();
+                FittedImputer::Grouped {
+                    medians,
+                    fallback: median,
+                }
+            }
+            ImputeStrategy::Regression => {
+                FittedImputer::Regression(Regression::fit(self.column, input_data)?)
+            }
+        };
+        self.fitted = Some(fitted);
+        Ok(())
+    }
+
+    /// The value of the column, or the fill value if it is missing.
+    pub fn impute(&self, input_data: &InputData) -> Result<f64> {
+        if let Some(value) = self.column.numeric(input_data) {
+            return Ok(value);
+        }
+        let fitted = self
+            .fitted
+            .as_ref()
+            .ok_or_else(|| anyhow!("the {} imputer is not fitted", self.column))?;
+        let value = match fitted {
+            FittedImputer::Constant(value) => *value,
+            FittedImputer::Grouped { medians, fallback } => {
+                let key = self.group_key(input_data);
+                medians
+                    .iter()
+                    .find(|(group, _)| *group == key)
+                    .map_or(*fallback, |(_, median)| *median)
+            }
+            FittedImputer::Regression(regression) => regression.predict(input_data),
+        };
+        Ok(value)
+    }
+
+    /// Name of the missing indicator feature, if it is enabled.
+    pub fn indicator_name(&self) -> Option<String> {
+        self.config
+            .indicator
+            .then(|| format!("{}_missing", self.column))
+    }
+
+    pub fn fitted(&self) -> Option<&FittedImputer> {
+        self.fitted.as_ref()
+    }
+
+    pub fn set_fitted(&mut self, fitted: FittedImputer) {
+        self.fitted = Some(fitted);
+    }
+
+    fn group_key(&self, input_data: &InputData) -> Vec<Option<String>> {
+        self.config
+            .group_by
+            .iter()
+            .map(|column| column.key(input_data))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    use crate::loader::{PassengerName, Pclass};
+    use crate::test_utils;
+
+    fn input_data(name: &str, pclass: Pclass, age: Option<f64>) -> InputData {
+        InputData {
+            pclass: Some(pclass),
+            name: Some(PassengerName::parse(name)),
+            age,
+            sibsp: Some(0),
+            parch: Some(0),
+            fare: Some(10.0),
+            ..test_utils::input_data(1)
+        }
+    }
+
+    fn train() -> Vec<InputData> {
+        vec![
+            input_data("A, Mr. A", Pclass::First, Some(40.0)),
+            input_data("B, Mr. B", Pclass::First, Some(50.0)),
+            input_data("C, Mr. C", Pclass::Third, Some(20.0)),
+            input_data("D, Master. D", Pclass::Third, Some(4.0)),
+            input_data("E, Master. E", Pclass::Third, Some(6.0)),
+            input_data("F, Mr. F", Pclass::Third, None),
+        ]
+    }
+
+    fn config(strategy: ImputeStrategy, group_by: Vec<Column>) -> ImputeConfig {
+        ImputeConfig {
+            strategy,
+            group_by,
+            indicator: false,
+        }
+    }
+
+    #[rstest]
+    #[case(ImputeStrategy::Mean, vec![], "Z, Mr. Z", Pclass::First, 24.0)]
+    #[case(ImputeStrategy::Median, vec![], "Z, Mr. Z", Pclass::First, 20.0)]
+    #[case(ImputeStrategy::GroupedMedian, vec![Column::Title], "Z, Master. Z", Pclass::First, 5.0)]
+    #[case(
+        ImputeStrategy::GroupedMedian,
+        vec![Column::Title, Column::Pclass],
+        "Z, Mr. Z",
+        Pclass::First,
+        45.0
+    )]
+    #[case(
+        ImputeStrategy::GroupedMedian,
+        vec![Column::Title, Column::Pclass],
+        "Z, Master. Z",
+        Pclass::First,
+        20.0
+    )]
+    fn test_Imputer_impute(
+        #[case] strategy: ImputeStrategy,
+        #[case] group_by: Vec<Column>,
+        #[case] name: &str,
+        #[case] pclass: Pclass,
+        #[case] expected: f64,
+    ) {
+        let mut imputer = Imputer::new(Column::Age, config(strategy, group_by));
+        imputer.fit(&train()).unwrap();
+        let actual = imputer.impute(&input_data(name, pclass, None)).unwrap();
+        assert_eq!(actual, expected);
+        let actual = imputer
+            .impute(&input_data(name, pclass, Some(33.0)))
+            .unwrap();
+        assert_eq!(actual, 33.0);
+    }
+
+    #[rstest]
+    fn test_Imputer_impute_regression() {
+        let mut imputer = Imputer::new(Column::Age, config(ImputeStrategy::Regression, vec![]));
+        imputer.fit(&train()).unwrap();
+        let master = imputer
+            .impute(&input_data("Z, Master. Z", Pclass::Third, None))
+            .unwrap();
+        let mr = imputer
+            .impute(&input_data("Z, Mr. Z", Pclass::First, None))
+            .unwrap();
+        assert!((master - 5.0).abs() < 0.5, "{}", master);
+        assert!((mr - 45.0).abs() < 0.5, "{}", mr);
+    }
+
+    #[rstest]
+    fn test_Imputer_impute_not_fitted() {
+        let imputer = Imputer::new(Column::Age, config(ImputeStrategy::Mean, vec![]));
+        let actual = imputer
+            .impute(&input_data("Z, Mr. Z", Pclass::First, None))
+            .err()
+            .unwrap();
+        assert_eq!(actual.to_string(), "the age imputer is not fitted");
+    }
+
+    #[rstest]
+    #[case(Column::Sex, config(ImputeStrategy::Mean, vec![]), "sex cannot be imputed; imputable columns: [\"age\", \"fare\"]")]
+    #[case(
+        Column::Age,
+        config(ImputeStrategy::GroupedMedian, vec![]),
+        "grouped_median imputation of age needs group_by"
+    )]
+    #[case(
+        Column::Age,
+        config(ImputeStrategy::GroupedMedian, vec![Column::Age]),
+        "age cannot be imputed grouped by itself"
+    )]
+    #[case(
+        Column::Age,
+        config(ImputeStrategy::Median, vec![Column::Title]),
+        "group_by is only used by grouped_median imputation"
+    )]
+    fn test_ImputeConfig_validate(
+        #[case] column: Column,
+        #[case] config: ImputeConfig,
+        #[case] expected: &str,
+    ) {
+        let actual = config.validate(column).err().unwrap();
+        assert_eq!(actual.to_string(), expected);
+    }
+}
@@ -0,0 +1,347 @@
+use std::sync::OnceLock;
+
+use anyhow::{bail, Result};
+
+/// A missing value, which LightGBM and XGBoost handle natively.
+pub const MISSING: f64 = f64::NAN;
+
+/// How the values of a feature are to be read; all values are stored as `f64`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FeatureType {
+    Float,
+    Integer,
+    /// 0 or 1.
+    Binary,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureField {
+    pub name: String,
+    pub dtype: FeatureType,
+    /// Whether the values are codes of unordered levels rather than quantities.
+    pub categorical: bool,
+}
+
+impl FeatureField {
+    pub fn float(name: impl Into<String>) -> Self {
+        Self::new(name, FeatureType::Float, false)
+    }
+
+    pub fn integer(name: impl Into<String>) -> Self {
+        Self::new(name, FeatureType::Integer, false)
+    }
+
+    pub fn binary(name: impl Into<String>) -> Self {
+        Self::new(name, FeatureType::Binary, false)
+    }
+
+    /// Integer codes of the levels of a categorical column.
+    pub fn categorical(name: impl Into<String>) -> Self {
+        Self::new(name, FeatureType::Integer, true)
+    }
+
+    fn new(name: impl Into<String>, dtype: FeatureType, categorical: bool) -> Self {
+        Self {
+            name: name.into(),
+            dtype,
+            categorical,
+        }
+    }
+}
+
+/// The columns of a [`FeatureMatrix`], with unique names.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FeatureSchema {
+    fields: Vec<FeatureField>,
+}
+
+impl FeatureSchema {
+    pub fn new(fields: Vec<FeatureField>) -> Result<Self> {
+        let mut schema = Self::default();
+        for field in fields {
+            schema.push(field)?;
+        }
+        Ok(schema)
+    }
+
+    pub fn push(&mut self, field: FeatureField) -> Result<()> {
+        if self.index_of(&field.name).is_some() {
+            bail!("duplicate feature {:?}", field.name);
+        }
+        self.fields.push(field);
+        Ok(())
+    }
+
+    pub fn fields(&self) -> &[FeatureField] {
+        &self.fields
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect()
+    }
+
+    /// The positions of the categorical features.
+    pub fn categorical_indices(&self) -> Vec<usize> {
+        self.fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.categorical)
+            .map(|(j, _)| j)
+            .collect()
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.fields.iter().position(|field| field.name == name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+/// Dense row-major features of many rows, described by one [`FeatureSchema`].
+///
+/// Missing values are [`MISSING`].
+///
+/// Backends read [`FeatureMatrix::values`], [`FeatureMatrix::values_f32`] or
+/// [`FeatureMatrix::rows`] directly instead of collecting per-row buffers.
+#[derive(Debug, Clone)]
+pub struct FeatureMatrix {
+    schema: FeatureSchema,
+    n_rows: usize,
+    values: Vec<f64>,
+    /// `values` as `f32`, converted on first use.
+    values_f32: OnceLock<Vec<f32>>,
+}
+
+impl PartialEq for FeatureMatrix {
+    fn eq(&self, other: &Self) -> bool {
+        self.schema == other.schema && self.n_rows == other.n_rows && self.values == other.values
+    }
+}
+
+impl FeatureMatrix {
+    /// A matrix without rows, to be filled with [`FeatureMatrix::push_row`].
+    pub fn new(schema: FeatureSchema) -> Self {
+        Self::with_capacity(schema, 0)
+    }
+
+    pub fn with_capacity(schema: FeatureSchema, n_rows: usize) -> Self {
+        let values = Vec::with_capacity(n_rows * schema.len());
+        Self {
+            schema,
+            n_rows: 0,
+            values,
+            values_f32: OnceLock::new(),
+        }
+    }
+
+    /// A matrix of `n_rows` rows without columns.
+    pub fn empty(n_rows: usize) -> Self {
+        Self {
+            schema: FeatureSchema::default(),
+            n_rows,
+            values: vec![],
+            values_f32: OnceLock::new(),
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `row` does not have a value for each column of the schema.
+    pub fn push_row(&mut self, row: &[f64]) {
+        assert_eq!(
+            row.len(),
+            self.schema.len(),
+            "row length does not match the schema"
+        );
+        self.values.extend_from_slice(row);
+        self.n_rows += 1;
+        self.values_f32.take();
+    }
+
+    /// The columns of `blocks` side by side; every block must have `n_rows` rows and other
+    /// feature names than the rest.
+    ///
+    /// The values are written once, each block at its column offset.
+    pub fn concat(n_rows: usize, blocks: &[FeatureMatrix]) -> Result<Self> {
+        let mut schema = FeatureSchema::default();
+        for block in blocks.iter() {
+            if block.n_rows != n_rows {
+                bail!(
+                    "cannot stack a matrix of {} rows onto one of {} rows",
+                    block.n_rows,
+                    n_rows
+                );
+            }
+            for field in block.schema.fields.iter() {
+                schema.push(field.clone())?;
+            }
+        }
+        let n_cols = schema.len();
+        let mut values = vec![0.0; n_rows * n_cols];
+        let mut offset = 0;
+        for block in blocks.iter() {
+            for (i, row) in block.rows().enumerate() {
+                let start = i * n_cols + offset;
+                values[start..start + row.len()].copy_from_slice(row);
+            }
+            offset += block.n_cols();
+        }
+        Ok(Self {
+            schema,
+            n_rows,
+            values,
+            values_f32: OnceLock::new(),
+        })
+    }
+
+    pub fn schema(&self) -> &FeatureSchema {
+        &self.schema
+    }
+
+    pub fn n_rows(&self) -> usize {
+        self.n_rows
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.schema.len()
+    }
+
+    /// All values, row after row.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// All values as `f32`, row after row, for backends that take `f32`; converted once and
+    /// cached.
+    pub fn values_f32(&self) -> &[f32] {
+        self.values_f32
+            .get_or_init(|| self.values.iter().map(|v| *v as f32).collect())
+    }
+
+    pub fn row(&self, i: usize) -> &[f64] {
+        let n_cols = self.n_cols();
+        &self.values[i * n_cols..(i + 1) * n_cols]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[f64]> {
+        (0..self.n_rows).map(|i| self.row(i))
+    }
+
+    pub fn column(&self, j: usize) -> impl Iterator<Item = f64> + '_ {
+        self.rows().map(move |row| row[j])
+    }
+
+    /// The names of the features with missing values.
+    pub fn missing_features(&self) -> Vec<&str> {
+        self.schema
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| self.column(*j).any(f64::is_nan))
+            .map(|(_, field)| field.name.as_str())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    fn matrix(names: &[&str], rows: &[&[f64]]) -> FeatureMatrix {
+        let schema = FeatureSchema::new(
+            names
+                .iter()
+                .map(|name| FeatureField::float(*name))
+                .collect(),
+        )
+        .unwrap();
+        let mut matrix = FeatureMatrix::new(schema);
+        for row in rows.iter() {
+            matrix.push_row(row);
+        }
+        matrix
+    }
+
+    #[rstest]
+    fn test_FeatureMatrix_push_row() {
+        let actual = matrix(&["age", "fare"], &[&[22.0, 7.25], &[38.0, 71.28]]);
+        assert_eq!(actual.n_rows(), 2);
+        assert_eq!(actual.n_cols(), 2);
+        assert_eq!(actual.values(), vec![22.0, 7.25, 38.0, 71.28]);
+        assert_eq!(actual.row(1), vec![38.0, 71.28]);
+        assert_eq!(actual.column(1).collect::<Vec<_>>(), vec![7.25, 71.28]);
+        assert!(actual.missing_features().is_empty());
+    }
+
+    #[rstest]
+    fn test_FeatureMatrix_concat() {
+        let blocks = [
+            matrix(&["age"], &[&[22.0], &[38.0]]),
+            matrix(&["fare", "sibsp"], &[&[7.25, 1.0], &[71.28, 0.0]]),
+        ];
+        let actual = FeatureMatrix::concat(2, &blocks).unwrap();
+        assert_eq!(actual.schema().names(), vec!["age", "fare", "sibsp"]);
+        assert_eq!(actual.values(), vec![22.0, 7.25, 1.0, 38.0, 71.28, 0.0]);
+        assert_eq!(
+            actual.values_f32(),
+            vec![22.0, 7.25, 1.0, 38.0, 71.28, 0.0f32]
+        );
+        assert_eq!(FeatureMatrix::concat(2, &[]).unwrap().n_rows(), 2);
+
+        let err = FeatureMatrix::concat(2, &[actual.clone(), blocks[0].clone()])
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "duplicate feature \"age\"");
+        let err = FeatureMatrix::concat(2, &[actual, matrix(&["parch"], &[&[1.0]])])
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "cannot stack a matrix of 1 rows onto one of 2 rows"
+        );
+    }
+
+    #[rstest]
+    fn test_FeatureMatrix_values_f32_after_push_row() {
+        let mut actual = matrix(&["age"], &[&[22.0]]);
+        assert_eq!(actual.values_f32(), vec![22.0f32]);
+        actual.push_row(&[38.0]);
+        assert_eq!(actual.values_f32(), vec![22.0, 38.0f32]);
+    }
+
+    #[rstest]
+    fn test_FeatureSchema_categorical_indices() {
+        let schema = FeatureSchema::new(vec![
+            FeatureField::categorical("pclass"),
+            FeatureField::float("age"),
+            FeatureField::binary("age_missing"),
+            FeatureField::categorical("title"),
+        ])
+        .unwrap();
+        assert_eq!(schema.categorical_indices(), vec![0, 3]);
+    }
+
+    #[rstest]
+    fn test_FeatureSchema_new_duplicate() {
+        let actual = FeatureSchema::new(vec![
+            FeatureField::float("age"),
+            FeatureField::categorical("age"),
+        ]);
+        assert_eq!(
+            actual.err().unwrap().to_string(),
+            "duplicate feature \"age\""
+        );
+    }
+}
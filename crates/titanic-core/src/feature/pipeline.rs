@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Result};
+
+use super::{
+    AbstractFeatureTransformer, CabinTransformer, FeatureMatrix, FeatureTransformer,
+    GroupSurvivalTransformer, GroupTransformer, Imputer, FEATURE_NAMES,
+};
+use crate::config::FeatureConfig;
+use crate::loader::InputData;
+
+/// Names in `features.names` that need no config: the [`FEATURE_NAMES`], which are built by
+/// one `raw` block, `cabin`, `groups` and `group_survival`, which has optional settings in
+/// `[features.group_survival]`.
+pub fn builtin_blocks() -> Vec<&'static str> {
+    FEATURE_NAMES
+        .iter()
+        .copied()
+        .chain(["cabin", "groups", "group_survival"])
+        .collect()
+}
+
+/// Named transformers whose features are concatenated in order.
+///
+/// Every block is fitted on the same data, and the feature names of all blocks must be unique.
+#[derive(Default)]
+pub struct FeaturePipeline {
+    blocks: Vec<(String, Box<dyn AbstractFeatureTransformer>)>,
+}
+
+impl FeaturePipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The blocks listed in `features.names`: built-in ones and the configured encoders.
+    ///
+    /// The [`FEATURE_NAMES`] among them are built by one [`FeatureTransformer`] block named
+    /// `raw`, with the imputers of their columns, in place of the first of them.
+    pub fn from_config(config: &FeatureConfig) -> Result<Self> {
+        let raw_names = config
+            .names
+            .iter()
+            .filter(|name| FEATURE_NAMES.contains(&name.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut pipeline = Self::new();
+        for name in config.names.iter() {
+            if raw_names.contains(name) {
+                if raw_names.first() == Some(name) {
+                    let mut transformer = FeatureTransformer::new(&raw_names)?;
+                    for name in raw_names.iter() {
+                        if let Some(impute) = config.impute.get(name) {
+                            let column = name.parse().map_err(|e: String| anyhow!(e))?;
+                            transformer =
+                                transformer.with_imputer(Imputer::new(column, impute.clone()));
+                        }
+                    }
+                    pipeline.push("raw", Box::new(transformer))?;
+                }
+                continue;
+            }
+            let block: Box<dyn AbstractFeatureTransformer> =
+                if let Some(encoder) = config.encoders.get(name) {
+                    encoder.build()
+                } else if name == "cabin" {
+                    Box::new(CabinTransformer)
+                } else if name == "groups" {
+                    Box::new(GroupTransformer::new())
+                } else if name == "group_survival" {
+                    Box::new(GroupSurvivalTransformer::new(config.group_survival))
+                } else {
+                    bail!("unknown feature: {}", name);
+                };
+            pipeline.push(name, block)?;
+        }
+        Ok(pipeline)
+    }
+
+    pub fn push(&mut self, name: &str, block: Box<dyn AbstractFeatureTransformer>) -> Result<()> {
+        if self.blocks.iter().any(|(block_name, _)| block_name == name) {
+            bail!("duplicate feature block {:?}", name);
+        }
+        self.blocks.push((name.to_string(), block));
+        Ok(())
+    }
+
+    pub fn block_names(&self) -> Vec<&str> {
+        self.blocks.iter().map(|(name, _)| name.as_str()).collect()
+    }
+}
+
+impl AbstractFeatureTransformer for FeaturePipeline {
+    fn fit(&mut self, input_data: &[InputData]) -> Result<()> {
+        for (_, block) in self.blocks.iter_mut() {
+            block.fit(input_data)?;
+        }
+        Ok(())
+    }
+
+    fn fit_unlabeled(&mut self, input_data: &[InputData]) -> Result<()> {
+        for (_, block) in self.blocks.iter_mut() {
+            block.fit_unlabeled(input_data)?;
+        }
+        Ok(())
+    }
+
+    fn transform(&self, input_data: &[InputData]) -> Result<FeatureMatrix> {
+        let mut matrices = Vec::with_capacity(self.blocks.len());
+        let mut owners = HashMap::new();
+        for (block_name, block) in self.blocks.iter() {
+            let block_matrix = block.transform(input_data)?;
+            for name in block_matrix.schema().names() {
+                if let Some(owner) = owners.insert(name.to_string(), block_name) {
+                    bail!(
+                        "feature {:?} is built by both the {} and {} blocks",
+                        name,
+                        owner,
+                        block_name
+                    );
+                }
+            }
+            matrices.push(block_matrix);
+        }
+        FeatureMatrix::concat(input_data.len(), &matrices)
+    }
+
+    /// The states of the blocks by name.
+    fn state(&self) -> Result<serde_json::Value> {
+        let mut state = serde_json::Map::new();
+        for (name, block) in self.blocks.iter() {
+            state.insert(name.clone(), block.state()?);
+        }
+        Ok(serde_json::Value::Object(state))
+    }
+
+    fn set_state(&mut self, mut state: serde_json::Value) -> Result<()> {
+        for (name, block) in self.blocks.iter_mut() {
+            let block_state = state
+                .get_mut(name.as_str())
+                .map(serde_json::Value::take)
+                .ok_or_else(|| anyhow!("the state has no {} block", name))?;
+            block.set_state(block_state)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use std::collections::BTreeMap;
+
+    use rstest::*;
+
+    use crate::feature::Column;
+    use crate::feature::{EncoderConfig, GroupSurvivalConfig, ImputeConfig, ImputeStrategy};
+    use crate::loader::{Embarked, Pclass, Sex};
+    use crate::test_utils;
+
+    fn input_data(passenger_id: u32, age: Option<f64>) -> InputData {
+        InputData {
+            survived: Some(passenger_id % 2),
+            pclass: Some(Pclass::Third),
+            sex: Some(Sex::Female),
+            age,
+            sibsp: Some(1),
+            parch: Some(0),
+            fare: Some(7.25),
+            embarked: Some(Embarked::S),
+            ..test_utils::input_data(passenger_id)
+        }
+    }
+
+    fn config(names: &[&str]) -> FeatureConfig {
+        FeatureConfig {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            impute: BTreeMap::from([(
+                "age".to_string(),
+                ImputeConfig {
+                    strategy: ImputeStrategy::Mean,
+                    group_by: vec![],
+                    indicator: true,
+                },
+            )]),
+            encoders: BTreeMap::from([(
+                "sex_onehot".to_string(),
+                EncoderConfig::OneHot {
+                    column: Column::Sex,
+                    min_count: 1,
+                },
+            )]),
+            group_survival: GroupSurvivalConfig::default(),
+        }
+    }
+
+    #[rstest]
+    fn test_FeaturePipeline_transform() {
+        let train = vec![input_data(1, Some(20.0)), input_data(2, Some(40.0))];
+        let mut pipeline =
+            FeaturePipeline::from_config(&config(&["age", "sex_onehot", "family_size"])).unwrap();
+        assert_eq!(pipeline.block_names(), vec!["raw", "sex_onehot"]);
+        pipeline.fit(&train).unwrap();
+        let actual = pipeline.transform(&[input_data(3, None)]).unwrap();
+        assert_eq!(
+            actual.schema().names(),
+            vec![
+                "age",
+                "family_size",
+                "age_missing",
+                "sex_female",
+                "sex_other"
+            ]
+        );
+        assert_eq!(actual.row(0), vec![30.0, 2.0, 1.0, 1.0, 0.0]);
+
+        let mut loaded =
+            FeaturePipeline::from_config(&config(&["age", "sex_onehot", "family_size"])).unwrap();
+        loaded.set_state(pipeline.state().unwrap()).unwrap();
+        assert_eq!(loaded.transform(&[input_data(3, None)]).unwrap(), actual);
+    }
+
+    #[rstest]
+    fn test_FeaturePipeline_transform_duplicate_feature() {
+        let mut pipeline = FeaturePipeline::new();
+        pipeline
+            .push("first", Box::new(FeatureTransformer::default()))
+            .unwrap();
+        pipeline.push("second", Box::new(CabinTransformer)).unwrap();
+        pipeline
+            .push(
+                "third",
+                Box::new(FeatureTransformer::new(&["sex".to_string()]).unwrap()),
+            )
+            .unwrap();
+        let actual = pipeline.transform(&[input_data(1, None)]).err().unwrap();
+        assert_eq!(
+            actual.to_string(),
+            "feature \"sex\" is built by both the first and third blocks"
+        );
+        let actual = pipeline
+            .push("first", Box::new(CabinTransformer))
+            .err()
+            .unwrap();
+        assert_eq!(actual.to_string(), "duplicate feature block \"first\"");
+    }
+
+    #[rstest]
+    fn test_FeaturePipeline_set_state_missing_block() {
+        let mut pipeline = FeaturePipeline::from_config(&config(&["age"])).unwrap();
+        let actual = pipeline.set_state(serde_json::json!({})).err().unwrap();
+        assert_eq!(actual.to_string(), "the state has no raw block");
+    }
+}
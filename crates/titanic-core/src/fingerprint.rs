@@ -0,0 +1,168 @@
+use std::fmt;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::loader::open_source;
+
+/// Identifies the contents of an input file and what the loader parsed from it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileFingerprint {
+    pub path: PathBuf,
+    /// SHA-256 of the file contents, after decompression.
+    pub sha256: String,
+    pub rows: usize,
+    /// SHA-256 of the parsed rows, which also changes when the parsing changes.
+    pub data_sha256: String,
+}
+
+impl FileFingerprint {
+    /// Hashes the file at `path` and `data`, the rows loaded from it.
+    pub fn new<P: AsRef<Path>, T: fmt::Debug>(path: P, data: &[T]) -> Result<Self> {
+        let path = path.as_ref();
+        let mut hasher = Sha256::new();
+        let mut reader = open_source(path)?;
+        let mut buf = [0; 8192];
+        loop {
+            let n = reader
+                .read(&mut buf)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            sha256: format!("{:x}", hasher.finalize()),
+            rows: data.len(),
+            data_sha256: hash_data(data),
+        })
+    }
+
+    /// Whether both describe the same data, wherever the files are.
+    pub fn same_data(&self, other: &Self) -> bool {
+        self.sha256 == other.sha256
+            && self.rows == other.rows
+            && self.data_sha256 == other.data_sha256
+    }
+}
+
+impl fmt::Display for FileFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} rows, sha256 {}, parsed sha256 {}",
+            self.path.display(),
+            self.rows,
+            &self.sha256[..12],
+            &self.data_sha256[..12]
+        )
+    }
+}
+
+/// Fingerprints of the data a run was trained and evaluated on, saved next to its models.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataFingerprint {
+    pub train: FileFingerprint,
+    pub test: FileFingerprint,
+    /// The sample submission and its passenger ids, if it could be read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_submission: Option<FileFingerprint>,
+}
+
+impl DataFingerprint {
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        serde_json::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))
+    }
+}
+
+/// SHA-256 of the `Debug` representation of each row.
+pub fn hash_data<T: fmt::Debug>(data: &[T]) -> String {
+    let mut hasher = Sha256::new();
+    for row in data.iter() {
+        hasher.update(format!("{:?}\n", row).as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    use crate::loader::{load_train_data, InputData};
+    use crate::test_utils::temp_path;
+
+    const CSV: &str =
+        "PassengerId,Survived,Pclass,Name,Sex,Age,SibSp,Parch,Ticket,Fare,Cabin,Embarked
+1,0,3,\"Braund, Mr. Owen Harris\",male,22,1,0,A/5 21171,7.25,,S
+";
+
+    #[rstest]
+    fn test_FileFingerprint_new() {
+        let path = temp_path("fingerprint", "new_train.csv");
+        fs::write(&path, CSV).unwrap();
+        let data = load_train_data(&path).unwrap();
+        let actual = FileFingerprint::new(&path, &data).unwrap();
+        assert_eq!(actual.rows, 1);
+        assert_eq!(actual.sha256, format!("{:x}", Sha256::digest(CSV)));
+        assert_eq!(actual.data_sha256, hash_data(&data));
+
+        let moved = temp_path("fingerprint", "new_moved.csv");
+        fs::write(&moved, CSV).unwrap();
+        assert!(actual.same_data(&FileFingerprint::new(&moved, &data).unwrap()));
+
+        let changed = temp_path("fingerprint", "new_changed.csv");
+        fs::write(&changed, CSV.replace(",22,", ",23,")).unwrap();
+        let changed_data = load_train_data(&changed).unwrap();
+        let changed = FileFingerprint::new(&changed, &changed_data).unwrap();
+        assert!(!actual.same_data(&changed));
+        assert_ne!(actual.data_sha256, changed.data_sha256);
+    }
+
+    #[rstest]
+    fn test_DataFingerprint_save_load() {
+        let path = temp_path("fingerprint", "save_load_train.csv");
+        fs::write(&path, CSV).unwrap();
+        let data = load_train_data(&path).unwrap();
+        let expected = DataFingerprint {
+            train: FileFingerprint::new(&path, &data).unwrap(),
+            test: FileFingerprint::new(&path, &[] as &[InputData]).unwrap(),
+            sample_submission: Some(FileFingerprint::new(&path, &[1]).unwrap()),
+        };
+        let fingerprint_path = temp_path("fingerprint", "save_load_fingerprint.json");
+        expected.save(&fingerprint_path).unwrap();
+        assert_eq!(DataFingerprint::load(&fingerprint_path).unwrap(), expected);
+    }
+
+    #[rstest]
+    fn test_DataFingerprint_load_without_sample_submission() {
+        let path = temp_path("fingerprint", "without_sample_submission_train.csv");
+        fs::write(&path, CSV).unwrap();
+        let fingerprint = FileFingerprint::new(&path, &load_train_data(&path).unwrap()).unwrap();
+        let fingerprint_path = temp_path("fingerprint", "without_sample_submission.json");
+        let json = serde_json::json!({"train": &fingerprint, "test": &fingerprint});
+        fs::write(&fingerprint_path, json.to_string()).unwrap();
+        let actual = DataFingerprint::load(&fingerprint_path).unwrap();
+        assert_eq!(actual.sample_submission, None);
+    }
+}
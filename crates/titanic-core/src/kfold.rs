@@ -1,6 +1,7 @@
 use rand::prelude::*;
 use std::collections::HashSet;
 
+use crate::config::{CvConfig, CvStrategy};
 use crate::loader::InputData;
 
 pub trait AbstractKFold {
@@ -87,6 +88,17 @@ impl AbstractKFold for StratifiedKFold {
     }
 }
 
+pub fn build_kfold(config: &CvConfig) -> Box<dyn AbstractKFold> {
+    match config.strategy {
+        CvStrategy::KFold => Box::new(KFold::new(config.n_splits, config.shuffle, config.seed)),
+        CvStrategy::Stratified => Box::new(StratifiedKFold::new(
+            config.n_splits,
+            config.shuffle,
+            config.seed,
+        )),
+    }
+}
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod tests {
@@ -94,20 +106,20 @@ mod tests {
 
     use rstest::*;
 
-    use crate::loader::{Embarked, Sex};
+    use crate::loader::{Embarked, PassengerName, Pclass, Sex, Ticket};
 
     #[fixture]
     pub fn fixture_input_data() -> InputData {
         InputData {
             passenger_id: 0,
             survived: None,
-            pclass: Some(3),
-            name: Some("Alice".to_string()),
+            pclass: Some(Pclass::Third),
+            name: Some(PassengerName::parse("Alice")),
             sex: Some(Sex::Female),
             age: Some(22.0),
             sibsp: Some(1),
             parch: Some(0),
-            ticket: Some("A/5 21171".to_string()),
+            ticket: Some(Ticket::parse("A/5 21171")),
             fare: Some(7.25),
             cabin: None,
             embarked: Some(Embarked::S),
@@ -119,7 +131,7 @@ mod tests {
         fn test(input_data: InputData) {
             let data = vec![input_data; 100];
             let mut labels = vec![0; 10];
-            labels.extend(vec![1; 90].iter().copied());
+            labels.extend([1; 90]);
             let mut rng = rand::thread_rng();
             labels.shuffle(&mut rng);
 
@@ -138,7 +150,7 @@ mod tests {
         fn test(input_data: InputData) {
             let data = vec![input_data; 100];
             let mut labels = vec![0; 10];
-            labels.extend(vec![1; 90].iter().copied());
+            labels.extend([1; 90]);
             let mut rng = rand::thread_rng();
             labels.shuffle(&mut rng);
 
@@ -148,7 +160,7 @@ mod tests {
             for index in folds.iter() {
                 let fold_labels = index.iter().map(|i| labels[*i]).collect::<Vec<_>>();
                 let mut expected = vec![0; 5];
-                expected.extend(vec![1; 45].iter().copied());
+                expected.extend([1; 45]);
                 assert_eq!(index.len(), 50);
                 assert_eq!(
                     fold_labels.iter().filter(|x| **x == 0).count(),
@@ -0,0 +1,16 @@
+pub mod cli;
+pub mod config;
+pub mod feature;
+pub mod fingerprint;
+pub mod kfold;
+pub mod loader;
+pub mod metrics;
+pub mod model;
+pub mod prediction;
+pub mod profile;
+pub mod runner;
+pub mod submission;
+#[cfg(test)]
+mod test_utils;
+pub mod utils;
+pub mod validation;
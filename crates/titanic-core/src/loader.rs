@@ -0,0 +1,885 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use csv::StringRecord;
+use thiserror::Error;
+
+mod cabin;
+#[cfg(feature = "parquet")]
+mod columnar;
+mod name;
+mod source;
+mod ticket;
+
+pub use cabin::{Cabin, CabinEntry};
+#[cfg(feature = "parquet")]
+use columnar::read_columnar;
+pub use name::{PassengerName, Title};
+pub use source::{open_csv, open_source};
+pub use ticket::Ticket;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Sex {
+    Female,
+    Male,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Pclass {
+    First = 1,
+    Second = 2,
+    Third = 3,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Embarked {
+    C, // Cherbourg
+    Q, // Queenstown
+    S, // Southampton
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputData {
+    pub passenger_id: u32,
+    pub survived: Option<u32>,
+    pub pclass: Option<Pclass>,
+    pub name: Option<PassengerName>,
+    pub sex: Option<Sex>,
+    pub age: Option<f64>,
+    pub sibsp: Option<i32>,
+    pub parch: Option<i32>,
+    pub ticket: Option<Ticket>,
+    pub fare: Option<f64>,
+    pub cabin: Option<Cabin>,
+    pub embarked: Option<Embarked>,
+}
+
+/// Location and raw text of a cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    /// 1-indexed line in a CSV file counting the header, or 1-indexed row in a Parquet or Arrow
+    /// IPC file.
+    pub line: u64,
+    pub column: &'static str,
+    pub value: String,
+}
+
+impl Cell {
+    pub fn new(line: u64, column: &'static str, value: &str) -> Self {
+        Self {
+            line,
+            column,
+            value: value.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {:?}",
+            self.line, self.column, self.value
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum LoaderError {
+    #[error("failed to read {path}: {message}")]
+    Read { path: PathBuf, message: String },
+    #[error("line {line}: malformed record: {message}")]
+    Record { line: u64, message: String },
+    #[error("missing column {column} in the header")]
+    MissingHeader { column: &'static str },
+    #[error("column {column} has unsupported type {data_type}")]
+    ColumnType {
+        column: &'static str,
+        data_type: String,
+    },
+    #[error("line {line}: missing column {column}")]
+    MissingColumn { line: u64, column: &'static str },
+    #[error("{0}: passenger id must be an unsigned integer")]
+    PassengerId(Cell),
+    #[error("{0}: survived must be 0 or 1")]
+    Survived(Cell),
+    #[error("{0}: pclass must be 1, 2 or 3")]
+    Pclass(Cell),
+    #[error("{0}: age must be a number")]
+    Age(Cell),
+    #[error("{0}: sibsp must be an integer")]
+    Sibsp(Cell),
+    #[error("{0}: parch must be an integer")]
+    Parch(Cell),
+    #[error("{0}: fare must be a number")]
+    Fare(Cell),
+}
+
+type Result<T> = std::result::Result<T, LoaderError>;
+
+impl LoaderError {
+    /// Line of the offending row, if known.
+    pub fn line(&self) -> Option<u64> {
+        match self {
+            LoaderError::Read { .. } | LoaderError::ColumnType { .. } => None,
+            LoaderError::MissingHeader { .. } => Some(1),
+            LoaderError::Record { line, .. } | LoaderError::MissingColumn { line, .. } => {
+                Some(*line)
+            }
+            LoaderError::PassengerId(cell)
+            | LoaderError::Survived(cell)
+            | LoaderError::Pclass(cell)
+            | LoaderError::Age(cell)
+            | LoaderError::Sibsp(cell)
+            | LoaderError::Parch(cell)
+            | LoaderError::Fare(cell) => Some(cell.line),
+        }
+    }
+
+    /// Column of the offending cell, if the error is about a single column.
+    pub fn column(&self) -> Option<&'static str> {
+        match self {
+            LoaderError::Read { .. } | LoaderError::Record { .. } => None,
+            LoaderError::MissingHeader { column }
+            | LoaderError::ColumnType { column, .. }
+            | LoaderError::MissingColumn { column, .. } => Some(column),
+            LoaderError::PassengerId(cell)
+            | LoaderError::Survived(cell)
+            | LoaderError::Pclass(cell)
+            | LoaderError::Age(cell)
+            | LoaderError::Sibsp(cell)
+            | LoaderError::Parch(cell)
+            | LoaderError::Fare(cell) => Some(cell.column),
+        }
+    }
+}
+
+/// Problems found by the lenient loaders.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DataQualityReport {
+    /// Number of data rows read, including dropped ones.
+    pub n_rows: usize,
+    /// Every problem, in file order.
+    pub errors: Vec<LoaderError>,
+    /// Lines of the rows that could not be loaded.
+    pub dropped_lines: Vec<u64>,
+}
+
+impl DataQualityReport {
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Number of invalid values per column.
+    pub fn invalid_counts(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for column in self.errors.iter().filter_map(LoaderError::column) {
+            *counts.entry(column).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// `Survived` cells other than 0 or 1.
+    pub fn invalid_survived(&self) -> Vec<&Cell> {
+        self.errors
+            .iter()
+            .filter_map(|e| match e {
+                LoaderError::Survived(cell) => Some(cell),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for DataQualityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} rows, {} problems, {} rows dropped",
+            self.n_rows,
+            self.errors.len(),
+            self.dropped_lines.len()
+        )?;
+        for (column, count) in self.invalid_counts() {
+            writeln!(f, "  {}: {} invalid values", column, count)?;
+        }
+        for e in self.errors.iter() {
+            writeln!(f, "  {}", e)?;
+        }
+        Ok(())
+    }
+}
+
+/// Columns read into `InputData`, in the order of the Kaggle train.csv.
+const COLUMNS: [&str; 12] = [
+    "PassengerId",
+    "Survived",
+    "Pclass",
+    "Name",
+    "Sex",
+    "Age",
+    "SibSp",
+    "Parch",
+    "Ticket",
+    "Fare",
+    "Cabin",
+    "Embarked",
+];
+
+/// Record positions of `COLUMNS`, looked up by header name.
+///
+/// Unknown columns are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ColumnMap {
+    positions: [Option<usize>; COLUMNS.len()],
+}
+
+impl ColumnMap {
+    /// `Survived` is required when `has_survived`, and skipped otherwise.
+    fn new(headers: &StringRecord, has_survived: bool) -> Result<Self> {
+        let mut positions = [None; COLUMNS.len()];
+        for (position, column) in positions.iter_mut().zip(COLUMNS) {
+            if column == "Survived" && !has_survived {
+                continue;
+            }
+            *position = headers.iter().position(|header| header.trim() == column);
+            if position.is_none() {
+                return Err(LoaderError::MissingHeader { column });
+            }
+        }
+        Ok(Self { positions })
+    }
+
+    fn has_survived(&self) -> bool {
+        self.positions[1].is_some()
+    }
+
+    /// Cell of `COLUMNS[index]` in `record`.
+    fn cell<'r>(&self, record: &'r StringRecord, index: usize, line: u64) -> Result<&'r str> {
+        self.positions[index]
+            .and_then(|position| record.get(position))
+            .ok_or(LoaderError::MissingColumn {
+                line,
+                column: COLUMNS[index],
+            })
+    }
+}
+
+/// Collects nullable-cell errors in lenient mode; `None` propagates them.
+type Issues<'a> = Option<&'a mut Vec<LoaderError>>;
+
+fn nullable<T>(result: Result<Option<T>>, issues: &mut Issues) -> Result<Option<T>> {
+    match (result, issues) {
+        (Err(e), Some(issues)) => {
+            issues.push(e);
+            Ok(None)
+        }
+        (result, _) => result,
+    }
+}
+
+impl InputData {
+    /// Parses a record of a train or test CSV.
+    ///
+    /// Invalid nullable cells are pushed to `issues` and read as missing when it is given;
+    /// an invalid `PassengerId` or `Survived` fails the record either way.
+    fn from_record(
+        record: &StringRecord,
+        line: u64,
+        columns: &ColumnMap,
+        mut issues: Issues,
+    ) -> Result<InputData> {
+        let cell = |index: usize| columns.cell(record, index, line);
+        let passenger_id = InputData::parse_passenger_id(cell(0)?, line)?;
+        let survived = if columns.has_survived() {
+            InputData::parse_survived(cell(1)?, line)?
+        } else {
+            None
+        };
+        let pclass = nullable(InputData::parse_pclass(cell(2)?, line), &mut issues)?;
+        let name = InputData::parse_name(cell(3)?)?;
+        let sex = InputData::parse_sex(cell(4)?)?;
+        let age = nullable(InputData::parse_age(cell(5)?, line), &mut issues)?;
+        let sibsp = nullable(InputData::parse_sibsp(cell(6)?, line), &mut issues)?;
+        let parch = nullable(InputData::parse_parch(cell(7)?, line), &mut issues)?;
+        let ticket = InputData::parse_ticket(cell(8)?)?;
+        let fare = nullable(InputData::parse_fare(cell(9)?, line), &mut issues)?;
+        let cabin = InputData::parse_cabin(cell(10)?)?;
+        let embarked = InputData::parse_embarked(cell(11)?)?;
+        Ok(InputData {
+            passenger_id,
+            survived,
+            pclass,
+            name,
+            sex,
+            age,
+            sibsp,
+            parch,
+            ticket,
+            fare,
+            cabin,
+            embarked,
+        })
+    }
+
+    fn parse_passenger_id(v: &str, line: u64) -> Result<u32> {
+        // * parse -> OK
+        // * cannot parse -> Err
+        v.parse::<u32>()
+            .map_err(|_| LoaderError::PassengerId(Cell::new(line, "PassengerId", v)))
+    }
+
+    fn parse_survived(v: &str, line: u64) -> Result<Option<u32>> {
+        // * parse and result is 0 or 1 -> OK
+        // * parse and result is not 0 or 1 -> Err
+        // * cannot parse -> Err
+        match v.parse::<u32>() {
+            Ok(0) => Ok(Some(0)),
+            Ok(1) => Ok(Some(1)),
+            _ => Err(LoaderError::Survived(Cell::new(line, "Survived", v))),
+        }
+    }
+
+    fn parse_pclass(v: &str, line: u64) -> Result<Option<Pclass>> {
+        // * 1 or 2 or 3 -> OK(Some)
+        // * empty string -> Ok(None)
+        // * otherwise -> Err
+        match v {
+            "" => Ok(None),
+            "1" => Ok(Some(Pclass::First)),
+            "2" => Ok(Some(Pclass::Second)),
+            "3" => Ok(Some(Pclass::Third)),
+            _ => Err(LoaderError::Pclass(Cell::new(line, "Pclass", v))),
+        }
+    }
+
+    fn parse_name(v: &str) -> Result<Option<PassengerName>> {
+        // * parse -> OK(Some)
+        // * empty string -> Ok(None)
+        if v.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(PassengerName::parse(v)))
+        }
+    }
+
+    fn parse_sex(v: &str) -> Result<Option<Sex>> {
+        // * female or male -> OK(Some)
+        // * neither female nor male -> Ok(None)
+        let sex = match v {
+            "female" => Some(Sex::Female),
+            "male" => Some(Sex::Male),
+            _ => None,
+        };
+        Ok(sex)
+    }
+
+    fn parse_age(v: &str, line: u64) -> Result<Option<f64>> {
+        // * parse -> OK(Some)
+        // * cannot parse due to empty string -> Ok(None)
+        // * cannot parse due to not float -> Err
+        if v.is_empty() {
+            Ok(None)
+        } else {
+            v.parse::<f64>()
+                .map(Some)
+                .map_err(|_| LoaderError::Age(Cell::new(line, "Age", v)))
+        }
+    }
+
+    fn parse_sibsp(v: &str, line: u64) -> Result<Option<i32>> {
+        // * parse -> OK(Some)
+        // * cannot parse due to empty string -> Ok(None)
+        // * cannot parse due to not integer -> Err
+        if v.is_empty() {
+            Ok(None)
+        } else {
+            v.parse::<i32>()
+                .map(Some)
+                .map_err(|_| LoaderError::Sibsp(Cell::new(line, "SibSp", v)))
+        }
+    }
+
+    fn parse_parch(v: &str, line: u64) -> Result<Option<i32>> {
+        // * parse -> OK(Some)
+        // * cannot parse due to empty string -> Ok(None)
+        // * cannot parse due to not integer -> Err
+        if v.is_empty() {
+            Ok(None)
+        } else {
+            v.parse::<i32>()
+                .map(Some)
+                .map_err(|_| LoaderError::Parch(Cell::new(line, "Parch", v)))
+        }
+    }
+
+    fn parse_ticket(v: &str) -> Result<Option<Ticket>> {
+        // * parse -> OK(Some)
+        // * empty string -> Ok(None)
+        if v.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(Ticket::parse(v)))
+        }
+    }
+
+    fn parse_fare(v: &str, line: u64) -> Result<Option<f64>> {
+        // * parse -> OK(Some)
+        // * cannot parse due to empty string -> Ok(None)
+        // * cannot parse due to not float -> Err
+        if v.is_empty() {
+            Ok(None)
+        } else {
+            v.parse::<f64>()
+                .map(Some)
+                .map_err(|_| LoaderError::Fare(Cell::new(line, "Fare", v)))
+        }
+    }
+
+    fn parse_cabin(v: &str) -> Result<Option<Cabin>> {
+        // * parse -> OK(Some)
+        // * empty string -> Ok(None)
+        if v.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(Cabin::parse(v)))
+        }
+    }
+
+    fn parse_embarked(v: &str) -> Result<Option<Embarked>> {
+        // * C or Q or S -> OK(Some)
+        // * neither C nor Q nor S -> Ok(None)
+        let embarked = match v {
+            "C" => Some(Embarked::C),
+            "Q" => Some(Embarked::Q),
+            "S" => Some(Embarked::S),
+            _ => None,
+        };
+        Ok(embarked)
+    }
+}
+
+/// Whether `path` is read as Parquet (`*.parquet`) or Arrow IPC (`*.arrow`, `*.ipc`,
+/// `*.feather`) instead of CSV.
+fn is_columnar(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| {
+        ["parquet", "arrow", "ipc", "feather"]
+            .iter()
+            .any(|columnar| ext.eq_ignore_ascii_case(columnar))
+    })
+}
+
+#[cfg(not(feature = "parquet"))]
+fn read_columnar(path: &Path) -> Result<(StringRecord, Vec<StringRecord>)> {
+    Err(LoaderError::Read {
+        path: path.to_path_buf(),
+        message: "Parquet and Arrow IPC support is not enabled; rebuild with `--features parquet`"
+            .to_string(),
+    })
+}
+
+/// Reads every record, calling `on_record` with the record, its line and the column map.
+fn read_records<P, F>(path: P, has_survived: bool, mut on_record: F) -> Result<()>
+where
+    P: AsRef<Path>,
+    F: FnMut(std::result::Result<StringRecord, LoaderError>, u64, &ColumnMap) -> Result<()>,
+{
+    let path = path.as_ref();
+    if is_columnar(path) {
+        let (headers, rows) = read_columnar(path)?;
+        let columns = ColumnMap::new(&headers, has_survived)?;
+        for (i, row) in rows.into_iter().enumerate() {
+            on_record(Ok(row), i as u64 + 1, &columns)?;
+        }
+        return Ok(());
+    }
+    let mut rdr = open_csv(path)?;
+    let headers = rdr.headers().map_err(|e| LoaderError::Record {
+        line: 1,
+        message: e.to_string(),
+    })?;
+    let columns = ColumnMap::new(headers, has_survived)?;
+    for (i, r) in rdr.records().enumerate() {
+        // the header is line 1
+        let fallback_line = i as u64 + 2;
+        let (record, line) = match r {
+            Ok(record) => {
+                let line = record
+                    .position()
+                    .map_or(fallback_line, |position| position.line());
+                (Ok(record), line)
+            }
+            Err(e) => {
+                let line = e
+                    .position()
+                    .map_or(fallback_line, |position| position.line());
+                let message = e.to_string();
+                (Err(LoaderError::Record { line, message }), line)
+            }
+        };
+        on_record(record, line, &columns)?;
+    }
+    Ok(())
+}
+
+fn load_data<P: AsRef<Path>>(path: P, has_survived: bool) -> Result<Vec<InputData>> {
+    let mut data = vec![];
+    read_records(path, has_survived, |record, line, columns| {
+        data.push(InputData::from_record(&record?, line, columns, None)?);
+        Ok(())
+    })?;
+    Ok(data)
+}
+
+fn load_data_lenient<P: AsRef<Path>>(
+    path: P,
+    has_survived: bool,
+) -> Result<(Vec<InputData>, DataQualityReport)> {
+    let mut data = vec![];
+    let mut report = DataQualityReport::default();
+    read_records(path, has_survived, |record, line, columns| {
+        report.n_rows += 1;
+        let input_data = record.and_then(|record| {
+            InputData::from_record(&record, line, columns, Some(&mut report.errors))
+        });
+        match input_data {
+            Ok(input_data) => data.push(input_data),
+            Err(e) => {
+                report.errors.push(e);
+                report.dropped_lines.push(line);
+            }
+        }
+        Ok(())
+    })?;
+    Ok((data, report))
+}
+
+/// Whether the data at `path` has a `Survived` column, i.e. is in the train format.
+pub fn has_survived_column<P: AsRef<Path>>(path: P) -> Result<bool> {
+    let path = path.as_ref();
+    let headers = if is_columnar(path) {
+        read_columnar(path)?.0
+    } else {
+        open_csv(path)?
+            .headers()
+            .map_err(|e| LoaderError::Record {
+                line: 1,
+                message: e.to_string(),
+            })?
+            .clone()
+    };
+    Ok(headers.iter().any(|header| header.trim() == "Survived"))
+}
+
+/// Loads data with the train.csv columns, matched by header name in any order.
+///
+/// CSV, gzip and zip inputs are read as described in [`open_csv`]; Parquet and Arrow IPC inputs
+/// need the `parquet` feature.
+pub fn load_train_data<P: AsRef<Path>>(path: P) -> Result<Vec<InputData>> {
+    load_data(path, true)
+}
+
+/// Loads a CSV with the test.csv columns; `Survived` is ignored if present.
+pub fn load_test_data<P: AsRef<Path>>(path: P) -> Result<Vec<InputData>> {
+    load_data(path, false)
+}
+
+/// Like `load_train_data`, but keeps going past invalid cells and rows.
+///
+/// Invalid nullable cells are read as missing; rows with a missing column, an invalid
+/// `PassengerId` or a `Survived` other than 0 or 1 are dropped. Only a file that cannot be
+/// opened or lacks a required header is an error.
+pub fn load_train_data_lenient<P: AsRef<Path>>(
+    path: P,
+) -> Result<(Vec<InputData>, DataQualityReport)> {
+    load_data_lenient(path, true)
+}
+
+/// Like `load_test_data`, but keeps going past invalid cells and rows.
+pub fn load_test_data_lenient<P: AsRef<Path>>(
+    path: P,
+) -> Result<(Vec<InputData>, DataQualityReport)> {
+    load_data_lenient(path, false)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    use crate::test_utils::temp_path;
+
+    fn assert_result_eq<T>(actual: Result<T>, expected: Result<T>)
+    where
+        T: std::fmt::Debug + PartialEq,
+    {
+        match (&actual, &expected) {
+            (Ok(actual), Ok(expected)) => assert_eq!(actual, expected),
+            (Err(actual), Err(expected)) => assert_eq!(actual.to_string(), expected.to_string()),
+            _ => panic!("unexpected result: {:?}, {:?}", actual, expected),
+        }
+    }
+
+    const TRAIN_HEADER: &str =
+        "PassengerId,Survived,Pclass,Name,Sex,Age,SibSp,Parch,Ticket,Fare,Cabin,Embarked";
+    const TEST_HEADER: &str =
+        "PassengerId,Pclass,Name,Sex,Age,SibSp,Parch,Ticket,Fare,Cabin,Embarked";
+    const REORDERED_HEADER: &str =
+        "Name,Extra,Embarked,Cabin,Fare,Ticket,Parch,SibSp,Age,Sex,Pclass,PassengerId,Survived";
+
+    fn record(line: &str) -> StringRecord {
+        let mut rdr = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(line.as_bytes());
+        rdr.records().next().unwrap().unwrap()
+    }
+
+    fn owen(survived: Option<u32>) -> InputData {
+        InputData {
+            passenger_id: 1,
+            survived,
+            pclass: Some(Pclass::Third),
+            name: Some(PassengerName::parse("Braund, Mr. Owen Harris")),
+            sex: Some(Sex::Male),
+            age: Some(22.0),
+            sibsp: Some(1),
+            parch: Some(0),
+            ticket: Some(Ticket::parse("A/5 21171")),
+            fare: Some(7.25),
+            cabin: None,
+            embarked: Some(Embarked::S),
+        }
+    }
+
+    #[rstest]
+    #[case(
+        TRAIN_HEADER,
+        true,
+        "1,0,3,\"Braund, Mr. Owen Harris\",male,22,1,0,A/5 21171,7.25,,S",
+        Ok(owen(Some(0)))
+    )]
+    #[case(
+        TRAIN_HEADER,
+        false,
+        "1,0,3,\"Braund, Mr. Owen Harris\",male,22,1,0,A/5 21171,7.25,,S",
+        Ok(owen(None))
+    )]
+    #[case(
+        TEST_HEADER,
+        false,
+        "1,3,\"Braund, Mr. Owen Harris\",male,22,1,0,A/5 21171,7.25,,S",
+        Ok(owen(None))
+    )]
+    #[case(
+        REORDERED_HEADER,
+        true,
+        "\"Braund, Mr. Owen Harris\",x,S,,7.25,A/5 21171,0,1,22,male,3,1,0",
+        Ok(owen(Some(0)))
+    )]
+    #[case(
+        TRAIN_HEADER,
+        true,
+        ",0,3,\"Braund, Mr. Owen Harris\",male,22,1,0,A/5 21171,7.25,,S",
+        Err(LoaderError::PassengerId(Cell::new(2, "PassengerId", "")))
+    )]
+    #[case(
+        TEST_HEADER,
+        false,
+        "1,3,\"Braund, Mr. Owen Harris\",male,a,1,0,A/5 21171,7.25,,S",
+        Err(LoaderError::Age(Cell::new(2, "Age", "a")))
+    )]
+    #[case(
+        TRAIN_HEADER,
+        true,
+        "1,0,3",
+        Err(LoaderError::MissingColumn{line: 2, column: "Name"})
+    )]
+    fn test_InputData_from_record(
+        #[case] header: &str,
+        #[case] has_survived: bool,
+        #[case] line: &str,
+        #[case] expected: Result<InputData>,
+    ) {
+        let columns = ColumnMap::new(&record(header), has_survived).unwrap();
+        let actual = InputData::from_record(&record(line), 2, &columns, None);
+        assert_result_eq(actual, expected);
+    }
+
+    #[rstest]
+    #[case(TEST_HEADER, true, "Survived")]
+    #[case("PassengerId,Survived,Pclass", true, "Name")]
+    #[case("Survived,Pclass", false, "PassengerId")]
+    fn test_ColumnMap_new_missing_header(
+        #[case] header: &str,
+        #[case] has_survived: bool,
+        #[case] column: &'static str,
+    ) {
+        let actual = ColumnMap::new(&record(header), has_survived);
+        assert_eq!(actual, Err(LoaderError::MissingHeader { column }));
+    }
+
+    #[rstest]
+    #[case("1", Ok(1))]
+    #[case("", Err(LoaderError::PassengerId(Cell::new(2, "PassengerId", ""))))]
+    fn test_InputData_parse_passenger_id(#[case] v: &str, #[case] expected: Result<u32>) {
+        let actual = InputData::parse_passenger_id(v, 2);
+        assert_result_eq(actual, expected);
+    }
+
+    #[rstest]
+    #[case("0", Ok(Some(0)))]
+    #[case("1", Ok(Some(1)))]
+    #[case("2", Err(LoaderError::Survived(Cell::new(2, "Survived", "2"))))]
+    #[case("", Err(LoaderError::Survived(Cell::new(2, "Survived", ""))))]
+    #[case("a", Err(LoaderError::Survived(Cell::new(2, "Survived", "a"))))]
+    fn test_InputData_parse_survived(#[case] v: &str, #[case] expected: Result<Option<u32>>) {
+        let actual = InputData::parse_survived(v, 2);
+        assert_result_eq(actual, expected);
+    }
+
+    #[rstest]
+    #[case("1", Ok(Some(Pclass::First)))]
+    #[case("3", Ok(Some(Pclass::Third)))]
+    #[case("", Ok(None))]
+    #[case("4", Err(LoaderError::Pclass(Cell::new(2, "Pclass", "4"))))]
+    #[case("a", Err(LoaderError::Pclass(Cell::new(2, "Pclass", "a"))))]
+    fn test_InputData_parse_pclass(#[case] v: &str, #[case] expected: Result<Option<Pclass>>) {
+        let actual = InputData::parse_pclass(v, 2);
+        assert_result_eq(actual, expected);
+    }
+
+    #[rstest]
+    #[case("Alice", Ok(Some(PassengerName::parse("Alice"))))]
+    #[case("", Ok(None))]
+    fn test_InputData_parse_name(#[case] v: &str, #[case] expected: Result<Option<PassengerName>>) {
+        let actual = InputData::parse_name(v);
+        assert_result_eq(actual, expected);
+    }
+
+    #[rstest]
+    #[case("female", Ok(Some(Sex::Female)))]
+    #[case("male", Ok(Some(Sex::Male)))]
+    #[case("", Ok(None))]
+    fn test_InputData_parse_sex(#[case] v: &str, #[case] expected: Result<Option<Sex>>) {
+        let actual = InputData::parse_sex(v);
+        assert_result_eq(actual, expected);
+    }
+
+    #[rstest]
+    #[case("20", Ok(Some(20.0)))]
+    #[case("", Ok(None))]
+    #[case("a", Err(LoaderError::Age(Cell::new(2, "Age", "a"))))]
+    fn test_InputData_parse_age(#[case] v: &str, #[case] expected: Result<Option<f64>>) {
+        let actual = InputData::parse_age(v, 2);
+        assert_result_eq(actual, expected);
+    }
+
+    #[rstest]
+    #[case("2", Ok(Some(2)))]
+    #[case("", Ok(None))]
+    #[case("a", Err(LoaderError::Sibsp(Cell::new(2, "SibSp", "a"))))]
+    fn test_InputData_parse_sibsp(#[case] v: &str, #[case] expected: Result<Option<i32>>) {
+        let actual = InputData::parse_sibsp(v, 2);
+        assert_result_eq(actual, expected);
+    }
+
+    #[rstest]
+    #[case("2", Ok(Some(2)))]
+    #[case("", Ok(None))]
+    #[case("a", Err(LoaderError::Parch(Cell::new(2, "Parch", "a"))))]
+    fn test_InputData_parse_parch(#[case] v: &str, #[case] expected: Result<Option<i32>>) {
+        let actual = InputData::parse_parch(v, 2);
+        assert_result_eq(actual, expected);
+    }
+
+    #[rstest]
+    #[case("A/5 21171", Ok(Some(Ticket::parse("A/5 21171"))))]
+    #[case("", Ok(None))]
+    fn test_InputData_parse_ticket(#[case] v: &str, #[case] expected: Result<Option<Ticket>>) {
+        let actual = InputData::parse_ticket(v);
+        assert_result_eq(actual, expected);
+    }
+
+    #[rstest]
+    #[case("7.25", Ok(Some(7.25)))]
+    #[case("", Ok(None))]
+    #[case("a", Err(LoaderError::Fare(Cell::new(2, "Fare", "a"))))]
+    fn test_InputData_parse_fare(#[case] v: &str, #[case] expected: Result<Option<f64>>) {
+        let actual = InputData::parse_fare(v, 2);
+        assert_result_eq(actual, expected);
+    }
+
+    #[rstest]
+    #[case("C85", Ok(Some(Cabin::parse("C85"))))]
+    #[case("", Ok(None))]
+    fn test_InputData_parse_cabin(#[case] v: &str, #[case] expected: Result<Option<Cabin>>) {
+        let actual = InputData::parse_cabin(v);
+        assert_result_eq(actual, expected);
+    }
+
+    #[rstest]
+    #[case("C", Ok(Some(Embarked::C)))]
+    #[case("Q", Ok(Some(Embarked::Q)))]
+    #[case("S", Ok(Some(Embarked::S)))]
+    #[case("", Ok(None))]
+    fn test_InputData_parse_embarked(#[case] v: &str, #[case] expected: Result<Option<Embarked>>) {
+        let actual = InputData::parse_embarked(v);
+        assert_result_eq(actual, expected);
+    }
+
+    #[rstest]
+    fn test_load_train_data_reports_line() {
+        let path = temp_path("loader", "reports_line.csv");
+        std::fs::write(
+            &path,
+            "PassengerId,Survived,Pclass,Name,Sex,Age,SibSp,Parch,Ticket,Fare,Cabin,Embarked\n\
+             1,0,3,\"Braund, Mr. Owen Harris\",male,22,1,0,A/5 21171,7.25,,S\n\
+             2,1,1,\"Cumings, Mrs. John Bradley\",female,38,1,0,PC 17599,x,C85,C\n",
+        )
+        .unwrap();
+        let actual = load_train_data(&path).err().unwrap();
+        assert_eq!(actual, LoaderError::Fare(Cell::new(3, "Fare", "x")));
+        assert_eq!(
+            actual.to_string(),
+            "line 3, column Fare: \"x\": fare must be a number"
+        );
+    }
+
+    #[rstest]
+    fn test_load_train_data_lenient() {
+        let path = temp_path("loader", "lenient.csv");
+        std::fs::write(
+            &path,
+            "PassengerId,Survived,Pclass,Name,Sex,Age,SibSp,Parch,Ticket,Fare,Cabin,Embarked\n\
+             1,0,3,Owen,male,a,1,0,A/5 21171,7.25,,S\n\
+             2,2,1,Florence,female,38,1,0,PC 17599,71.28,C85,C\n\
+             x,1,3,Laina,female,26,0,0,STON/O2. 3101282,7.92,,S\n\
+             4,1,1\n\
+             5,0,3,William,male,35,0,0,373450,b,,S\n",
+        )
+        .unwrap();
+        let (data, report) = load_train_data_lenient(&path).unwrap();
+        assert_eq!(
+            data.iter().map(|x| x.passenger_id).collect::<Vec<_>>(),
+            vec![1, 5]
+        );
+        assert_eq!(data[0].age, None);
+        assert_eq!(data[1].fare, None);
+        assert_eq!(report.n_rows, 5);
+        assert_eq!(report.dropped_lines, vec![3, 4, 5]);
+        assert_eq!(
+            report.invalid_survived(),
+            vec![&Cell::new(3, "Survived", "2")]
+        );
+        assert_eq!(
+            report.invalid_counts(),
+            BTreeMap::from([("Age", 1), ("Fare", 1), ("PassengerId", 1), ("Survived", 1)])
+        );
+        assert_eq!(report.errors.len(), 5);
+        assert!(!report.is_clean());
+    }
+}
@@ -0,0 +1,83 @@
+/// One cabin of a `Cabin` cell.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CabinEntry {
+    pub deck: char,
+    /// `None` for deck-only values such as `F` in `F G73`.
+    pub number: Option<u32>,
+}
+
+/// A `Cabin` cell, which may list several cabins, e.g. `C23 C25 C27`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cabin {
+    /// The cabin as written in the CSV.
+    pub full: String,
+    pub entries: Vec<CabinEntry>,
+}
+
+impl Cabin {
+    /// Tokens that do not start with a letter are skipped.
+    pub fn parse(full: &str) -> Self {
+        let entries = full
+            .split_whitespace()
+            .filter_map(|token| {
+                let mut chars = token.chars();
+                let deck = chars.next().filter(char::is_ascii_alphabetic)?;
+                Some(CabinEntry {
+                    deck: deck.to_ascii_uppercase(),
+                    number: chars.as_str().parse().ok(),
+                })
+            })
+            .collect();
+        Self {
+            full: full.to_string(),
+            entries,
+        }
+    }
+
+    /// Deck of the first cabin with a number, else of the first cabin.
+    pub fn deck(&self) -> Option<char> {
+        self.entries
+            .iter()
+            .find(|entry| entry.number.is_some())
+            .or_else(|| self.entries.first())
+            .map(|entry| entry.deck)
+    }
+
+    /// Number of cabins with a number.
+    pub fn count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.number.is_some())
+            .count()
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    #[rstest]
+    #[case("C85", vec![('C', Some(85))], Some('C'), 1)]
+    #[case("C23 C25 C27", vec![('C', Some(23)), ('C', Some(25)), ('C', Some(27))], Some('C'), 3)]
+    #[case("F G73", vec![('F', None), ('G', Some(73))], Some('G'), 1)]
+    #[case("T", vec![('T', None)], Some('T'), 0)]
+    #[case("", vec![], None, 0)]
+    fn test_Cabin_parse(
+        #[case] full: &str,
+        #[case] entries: Vec<(char, Option<u32>)>,
+        #[case] deck: Option<char>,
+        #[case] count: usize,
+    ) {
+        let actual = Cabin::parse(full);
+        let expected = entries
+            .into_iter()
+            .map(|(deck, number)| CabinEntry { deck, number })
+            .collect::<Vec<_>>();
+        assert_eq!(actual.entries, expected);
+        assert_eq!(actual.deck(), deck);
+        assert_eq!(actual.count(), count);
+    }
+}
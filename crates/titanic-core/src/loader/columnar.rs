@@ -0,0 +1,266 @@
+use std::fs::File;
+use std::path::Path;
+
+use ::arrow::array::{Array, ArrayRef, AsArray};
+use ::arrow::compute::cast;
+use ::arrow::datatypes::{DataType, Float64Type, Schema, SchemaRef};
+use ::arrow::error::ArrowError;
+use ::arrow::ipc::reader::FileReader;
+use ::arrow::record_batch::RecordBatch;
+use csv::StringRecord;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+use super::{LoaderError, Result, COLUMNS};
+
+/// Columns read as text; the other known columns must be numeric.
+const TEXT_COLUMNS: [&str; 5] = ["Name", "Sex", "Ticket", "Cabin", "Embarked"];
+/// Numeric columns whose values must be integers.
+const INTEGER_COLUMNS: [&str; 5] = ["PassengerId", "Survived", "Pclass", "SibSp", "Parch"];
+
+/// Reads a Parquet or Arrow IPC file as a header and rows of text cells, so that the rows go
+/// through the same parsing as CSV records.
+///
+/// Unknown columns are read as empty cells.
+pub(super) fn read_columnar(path: &Path) -> Result<(StringRecord, Vec<StringRecord>)> {
+    let read_error = |message: String| LoaderError::Read {
+        path: path.to_path_buf(),
+        message,
+    };
+    let (schema, batches) = read_batches(path).map_err(|e| read_error(e.to_string()))?;
+    check_schema(&schema)?;
+
+    let headers = schema
+        .fields()
+        .iter()
+        .map(|field| field.name().as_str())
+        .collect::<StringRecord>();
+    let mut rows = vec![];
+    for batch in batches.iter() {
+        let columns = schema
+            .fields()
+            .iter()
+            .zip(batch.columns())
+            .map(|(field, array)| {
+                if COLUMNS.contains(&field.name().as_str()) {
+                    to_text(field.name(), array).map(Some)
+                } else {
+                    Ok(None)
+                }
+            })
+            .collect::<std::result::Result<Vec<_>, ArrowError>>()
+            .map_err(|e| read_error(e.to_string()))?;
+        for row in 0..batch.num_rows() {
+            rows.push(
+                columns
+                    .iter()
+                    .map(|column| column.as_ref().map_or("", |column| column[row].as_str()))
+                    .collect::<StringRecord>(),
+            );
+        }
+    }
+    Ok((headers, rows))
+}
+
+fn read_batches(path: &Path) -> std::result::Result<(SchemaRef, Vec<RecordBatch>), ArrowError> {
+    let file = File::open(path)?;
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("parquet"))
+    {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file)
+            .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+        let schema = builder.schema().clone();
+        let batches = builder
+            .build()
+            .map_err(|e| ArrowError::ExternalError(Box::new(e)))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok((schema, batches))
+    } else {
+        let reader = FileReader::try_new(file, None)?;
+        let schema = reader.schema();
+        let batches = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok((schema, batches))
+    }
+}
+
+/// Checks the types of the known columns; missing columns are reported by the column mapping.
+fn check_schema(schema: &Schema) -> Result<()> {
+    for column in COLUMNS {
+        let Ok(field) = schema.field_with_name(column) else {
+            continue;
+        };
+        let data_type = field.data_type();
+        let ok = if TEXT_COLUMNS.contains(&column) {
+            is_text(data_type)
+        } else {
+            data_type.is_numeric()
+        };
+        if !ok && *data_type != DataType::Null {
+            return Err(LoaderError::ColumnType {
+                column,
+                data_type: data_type.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn is_text(data_type: &DataType) -> bool {
+    match data_type {
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => true,
+        DataType::Dictionary(_, value_type) => is_text(value_type),
+        _ => false,
+    }
+}
+
+/// Formats the cells of `array`; nulls become empty strings.
+///
+/// Whole floats in integer columns, which pandas writes for integer columns with nulls, are
+/// formatted without a fraction.
+fn to_text(column: &str, array: &ArrayRef) -> std::result::Result<Vec<String>, ArrowError> {
+    if INTEGER_COLUMNS.contains(&column) && array.data_type().is_floating() {
+        let array = cast(array, &DataType::Float64)?;
+        let array = array.as_primitive::<Float64Type>();
+        return Ok((0..array.len())
+            .map(|i| {
+                if array.is_null(i) {
+                    String::new()
+                } else if array.value(i).fract() == 0.0 {
+                    format!("{}", array.value(i) as i64)
+                } else {
+                    array.value(i).to_string()
+                }
+            })
+            .collect());
+    }
+    let array = cast(array, &DataType::Utf8)?;
+    let array = array.as_string::<i32>();
+    Ok((0..array.len())
+        .map(|i| {
+            if array.is_null(i) {
+                String::new()
+            } else {
+                array.value(i).to_string()
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use ::arrow::array::{Float64Array, Int64Array, StringArray};
+    use ::arrow::datatypes::Field;
+    use ::arrow::ipc::writer::FileWriter;
+    use parquet::arrow::ArrowWriter;
+    use rstest::*;
+
+    use crate::loader::{load_test_data, load_train_data, Cell, PassengerName, Sex};
+    use crate::test_utils::temp_path;
+
+    fn batch(age: ArrayRef) -> RecordBatch {
+        let text = |v: &str| Arc::new(StringArray::from(vec![Some(v), None])) as ArrayRef;
+        let columns: Vec<(&str, ArrayRef)> = vec![
+            ("PassengerId", Arc::new(Int64Array::from(vec![1, 2]))),
+            ("Extra", Arc::new(Int64Array::from(vec![0, 0]))),
+            ("Survived", Arc::new(Int64Array::from(vec![0, 1]))),
+            ("Pclass", Arc::new(Int64Array::from(vec![3, 1]))),
+            ("Name", text("Braund, Mr. Owen Harris")),
+            ("Sex", text("male")),
+            ("Age", age),
+            ("SibSp", Arc::new(Float64Array::from(vec![Some(1.0), None]))),
+            ("Parch", Arc::new(Int64Array::from(vec![0, 0]))),
+            ("Ticket", text("A/5 21171")),
+            ("Fare", Arc::new(Float64Array::from(vec![7.25, 71.2833]))),
+            ("Cabin", text("C85")),
+            ("Embarked", text("S")),
+        ];
+        let schema = Schema::new(
+            columns
+                .iter()
+                .map(|(name, array)| Field::new(*name, array.data_type().clone(), true))
+                .collect::<Vec<_>>(),
+        );
+        RecordBatch::try_new(
+            Arc::new(schema),
+            columns.into_iter().map(|(_, array)| array).collect(),
+        )
+        .unwrap()
+    }
+
+    fn write(path: &Path, batch: &RecordBatch) {
+        let file = File::create(path).unwrap();
+        if path.extension().unwrap().eq_ignore_ascii_case("parquet") {
+            let mut writer = ArrowWriter::try_new(file, batch.schema(), None).unwrap();
+            writer.write(batch).unwrap();
+            writer.close().unwrap();
+        } else {
+            let mut writer = FileWriter::try_new(file, &batch.schema()).unwrap();
+            writer.write(batch).unwrap();
+            writer.finish().unwrap();
+        }
+    }
+
+    #[rstest]
+    #[case("train.parquet")]
+    #[case("train_upper.PARQUET")]
+    #[case("train.arrow")]
+    fn test_load_train_data_columnar(#[case] name: &str) {
+        let path = temp_path("columnar", name);
+        write(
+            &path,
+            &batch(Arc::new(Float64Array::from(vec![Some(22.0), None]))),
+        );
+
+        let actual = load_train_data(&path).unwrap();
+        assert_eq!(actual.len(), 2);
+        assert_eq!(actual[0].passenger_id, 1);
+        assert_eq!(actual[0].survived, Some(0));
+        assert_eq!(
+            actual[0].name,
+            Some(PassengerName::parse("Braund, Mr. Owen Harris"))
+        );
+        assert_eq!(actual[0].sex, Some(Sex::Male));
+        assert_eq!(actual[0].age, Some(22.0));
+        assert_eq!(actual[0].sibsp, Some(1));
+        assert_eq!(actual[1].survived, Some(1));
+        assert_eq!(actual[1].name, None);
+        assert_eq!(actual[1].age, None);
+        assert_eq!(actual[1].sibsp, None);
+        assert_eq!(actual[1].fare, Some(71.2833));
+
+        let actual = load_test_data(&path).unwrap();
+        assert_eq!(actual[1].survived, None);
+    }
+
+    #[rstest]
+    fn test_load_train_data_columnar_type_mismatch() {
+        let path = temp_path("columnar", "type_mismatch.parquet");
+        write(&path, &batch(Arc::new(StringArray::from(vec!["22", "38"]))));
+        let actual = load_train_data(&path).err().unwrap();
+        assert_eq!(
+            actual,
+            LoaderError::ColumnType {
+                column: "Age",
+                data_type: "Utf8".to_string()
+            }
+        );
+    }
+
+    #[rstest]
+    fn test_load_train_data_columnar_invalid_value() {
+        let path = temp_path("columnar", "invalid_value.arrow");
+        let mut columns = batch(Arc::new(Float64Array::from(vec![22.0, 38.0])))
+            .columns()
+            .to_vec();
+        columns[2] = Arc::new(Int64Array::from(vec![0, 2]));
+        let batch = RecordBatch::try_new(batch(columns[6].clone()).schema(), columns).unwrap();
+        write(&path, &batch);
+        let actual = load_train_data(&path).err().unwrap();
+        assert_eq!(actual, LoaderError::Survived(Cell::new(2, "Survived", "2")));
+    }
+}
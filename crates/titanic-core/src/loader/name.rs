@@ -0,0 +1,323 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Normalized honorific of a passenger name.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Title {
+    Mr,
+    Mrs,  // also Mme
+    Miss, // also Mlle and Ms
+    Master,
+    Dr,
+    Rev,
+    Officer, // Capt, Col, Major
+    Noble,   // Countess, Don, Dona, Jonkheer, Lady, Sir
+    Other,
+}
+
+impl FromStr for Title {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let title = match s.trim().trim_end_matches('.').to_lowercase().as_str() {
+            "mr" => Title::Mr,
+            "mrs" | "mme" => Title::Mrs,
+            "miss" | "mlle" | "ms" => Title::Miss,
+            "master" => Title::Master,
+            "dr" => Title::Dr,
+            "rev" => Title::Rev,
+            "capt" | "col" | "major" => Title::Officer,
+            "countess" | "the countess" | "don" | "dona" | "jonkheer" | "lady" | "sir" => {
+                Title::Noble
+            }
+            _ => Title::Other,
+        };
+        Ok(title)
+    }
+}
+
+impl fmt::Display for Title {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// A Kaggle passenger name such as `Surname, Title. Given Names "Nickname" (Maiden Name)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassengerName {
+    /// The name as written in the CSV.
+    pub full: String,
+    pub surname: Option<String>,
+    pub title: Option<Title>,
+    pub given_names: Option<String>,
+    /// Unquoted text of the first parenthesized part, usually a married woman's own name.
+    pub maiden_name: Option<String>,
+    /// First quoted part, inside or outside the parentheses.
+    pub nickname: Option<String>,
+}
+
+impl PassengerName {
+    /// Splits `full` into its parts; parts that are absent are `None`.
+    pub fn parse(full: &str) -> Self {
+        let (surname, rest) = match full.split_once(',') {
+            Some((surname, rest)) => (non_empty(surname), rest),
+            None => (None, full),
+        };
+        let (title, rest) = match rest.split_once('.') {
+            Some((title, rest)) if !title.contains(['(', '"']) => {
+                (Some(title.parse().unwrap()), rest)
+            }
+            _ => (None, rest),
+        };
+
+        let mut outside = String::new();
+        let mut parenthesized = vec![];
+        let mut depth = 0;
+        for c in rest.chars() {
+            match c {
+                '(' => {
+                    if depth == 0 {
+                        parenthesized.push(String::new());
+                    }
+                    depth += 1;
+                }
+                ')' if depth > 0 => depth -= 1,
+                _ if depth > 0 => parenthesized.last_mut().unwrap().push(c),
+                _ => outside.push(c),
+            }
+        }
+
+        let (given_names, mut nickname) = split_quoted(&outside);
+        let mut maiden_name = None;
+        for part in parenthesized.iter() {
+            let (unquoted, quoted) = split_quoted(part);
+            if maiden_name.is_none() {
+                maiden_name = unquoted;
+            }
+            if nickname.is_none() {
+                nickname = quoted;
+            }
+        }
+
+        Self {
+            full: full.to_string(),
+            surname,
+            title,
+            given_names,
+            maiden_name,
+            nickname,
+        }
+    }
+}
+
+/// Splits `s` into its unquoted text and its first quoted part.
+fn split_quoted(s: &str) -> (Option<String>, Option<String>) {
+    let mut unquoted = String::new();
+    let mut quoted = vec![];
+    for (i, part) in s.split('"').enumerate() {
+        if i % 2 == 0 {
+            unquoted.push_str(part);
+            unquoted.push(' ');
+        } else {
+            quoted.push(part);
+        }
+    }
+    (non_empty(&unquoted), quoted.into_iter().find_map(non_empty))
+}
+
+/// `s` with runs of whitespace collapsed, or `None` if it is blank.
+fn non_empty(s: &str) -> Option<String> {
+    let s = s.split_whitespace().collect::<Vec<_>>().join(" ");
+    if s.is_empty() {
+        None
+    } else {
+        Some(s)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    fn name(
+        full: &str,
+        surname: &str,
+        title: Option<Title>,
+        given_names: Option<&str>,
+        maiden_name: Option<&str>,
+        nickname: Option<&str>,
+    ) -> PassengerName {
+        PassengerName {
+            full: full.to_string(),
+            surname: Some(surname.to_string()),
+            title,
+            given_names: given_names.map(str::to_string),
+            maiden_name: maiden_name.map(str::to_string),
+            nickname: nickname.map(str::to_string),
+        }
+    }
+
+    #[rstest]
+    #[case(
+        "Braund, Mr. Owen Harris",
+        "Braund",
+        Some(Title::Mr),
+        Some("Owen Harris"),
+        None,
+        None
+    )]
+    #[case(
+        "Cumings, Mrs. John Bradley (Florence Briggs Thayer)",
+        "Cumings",
+        Some(Title::Mrs),
+        Some("John Bradley"),
+        Some("Florence Briggs Thayer"),
+        None
+    )]
+    #[case(
+        "Heikkinen, Miss. Laina",
+        "Heikkinen",
+        Some(Title::Miss),
+        Some("Laina"),
+        None,
+        None
+    )]
+    #[case(
+        "O'Dwyer, Miss. Ellen \"Nellie\"",
+        "O'Dwyer",
+        Some(Title::Miss),
+        Some("Ellen"),
+        None,
+        Some("Nellie")
+    )]
+    #[case(
+        "Watt, Mrs. James (Elizabeth \"Bessie\" Inglis Milne)",
+        "Watt",
+        Some(Title::Mrs),
+        Some("James"),
+        Some("Elizabeth Inglis Milne"),
+        Some("Bessie")
+    )]
+    #[case(
+        "Rothes, the Countess. of (Lucy Noel Martha Dyer-Edwards)",
+        "Rothes",
+        Some(Title::Noble),
+        Some("of"),
+        Some("Lucy Noel Martha Dyer-Edwards"),
+        None
+    )]
+    #[case(
+        "Duff Gordon, Lady. (Lucille Christiana Sutherland) (\"Mrs Morgan\")",
+        "Duff Gordon",
+        Some(Title::Noble),
+        None,
+        Some("Lucille Christiana Sutherland"),
+        Some("Mrs Morgan")
+    )]
+    #[case(
+        "Mayne, Mlle. Berthe Antonine (\"Mrs de Villiers\")",
+        "Mayne",
+        Some(Title::Miss),
+        Some("Berthe Antonine"),
+        None,
+        Some("Mrs de Villiers")
+    )]
+    #[case(
+        "Bystrom, Mrs. (Karolina)",
+        "Bystrom",
+        Some(Title::Mrs),
+        None,
+        Some("Karolina"),
+        None
+    )]
+    #[case(
+        "Simonius-Blumer, Col. Oberst Alfons",
+        "Simonius-Blumer",
+        Some(Title::Officer),
+        Some("Oberst Alfons"),
+        None,
+        None
+    )]
+    #[case(
+        "Lahtinen, Rev. William",
+        "Lahtinen",
+        Some(Title::Rev),
+        Some("William"),
+        None,
+        None
+    )]
+    #[case(
+        "Aubart, Mme. Leontine Pauline",
+        "Aubart",
+        Some(Title::Mrs),
+        Some("Leontine Pauline"),
+        None,
+        None
+    )]
+    #[case(
+        "Reynaldo, Ms. Encarnacion",
+        "Reynaldo",
+        Some(Title::Miss),
+        Some("Encarnacion"),
+        None,
+        None
+    )]
+    #[case(
+        "Oliva y Ocana, Dona. Fermina",
+        "Oliva y Ocana",
+        Some(Title::Noble),
+        Some("Fermina"),
+        None,
+        None
+    )]
+    #[case(
+        "Uruchurtu, Don. Manuel E",
+        "Uruchurtu",
+        Some(Title::Noble),
+        Some("Manuel E"),
+        None,
+        None
+    )]
+    #[case(
+        "Minahan, Dr. William Edward",
+        "Minahan",
+        Some(Title::Dr),
+        Some("William Edward"),
+        None,
+        None
+    )]
+    #[case("Smith, Jr James", "Smith", None, Some("Jr James"), None, None)]
+    fn test_PassengerName_parse(
+        #[case] full: &str,
+        #[case] surname: &str,
+        #[case] title: Option<Title>,
+        #[case] given_names: Option<&str>,
+        #[case] maiden_name: Option<&str>,
+        #[case] nickname: Option<&str>,
+    ) {
+        let actual = PassengerName::parse(full);
+        let expected = name(full, surname, title, given_names, maiden_name, nickname);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    fn test_PassengerName_parse_without_comma() {
+        let actual = PassengerName::parse("Mr. Nobody");
+        assert_eq!(actual.surname, None);
+        assert_eq!(actual.title, Some(Title::Mr));
+        assert_eq!(actual.given_names, Some("Nobody".to_string()));
+    }
+
+    #[rstest]
+    #[case("Mr", Title::Mr)]
+    #[case("Mlle.", Title::Miss)]
+    #[case("the Countess", Title::Noble)]
+    #[case("Major", Title::Officer)]
+    #[case("Prof", Title::Other)]
+    fn test_Title_from_str(#[case] s: &str, #[case] expected: Title) {
+        assert_eq!(s.parse::<Title>().unwrap(), expected);
+    }
+}
@@ -0,0 +1,174 @@
+use std::fs::File;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use csv::Reader;
+use flate2::read::GzDecoder;
+use zip::ZipArchive;
+
+use super::{LoaderError, Result};
+
+/// Opens a CSV, reading through compression and archives as in [`open_source`].
+pub fn open_csv<P: AsRef<Path>>(path: P) -> Result<Reader<Box<dyn Read>>> {
+    Ok(Reader::from_reader(open_source(path)?))
+}
+
+/// Opens a file for reading, through compression and archives.
+///
+/// * `*.gz` files are decompressed; `train.csv` falls back to `train.csv.gz` if it is missing.
+/// * A path inside a `*.zip` file, e.g. `titanic.zip/train.csv`, reads that member, matched by
+///   file name if the archive nests it in a directory.
+pub fn open_source<P: AsRef<Path>>(path: P) -> Result<Box<dyn Read>> {
+    let path = path.as_ref();
+    let read_error = |message: String| LoaderError::Read {
+        path: path.to_path_buf(),
+        message,
+    };
+    let reader: Box<dyn Read> = if path.is_file() {
+        open_file(path).map_err(|e| read_error(e.to_string()))?
+    } else if let Some(gz_path) = gz_path(path).filter(|gz_path| gz_path.is_file()) {
+        open_file(&gz_path).map_err(|e| read_error(e.to_string()))?
+    } else if let Some((archive, member)) = split_zip_path(path) {
+        Box::new(Cursor::new(
+            read_zip_member(&archive, &member).map_err(read_error)?,
+        ))
+    } else {
+        return Err(read_error("no such file".to_string()));
+    };
+    Ok(reader)
+}
+
+/// `path` with `.gz` appended.
+fn gz_path(path: &Path) -> Option<PathBuf> {
+    let mut file_name = path.file_name()?.to_os_string();
+    file_name.push(".gz");
+    Some(path.with_file_name(file_name))
+}
+
+fn is_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+fn open_file(path: &Path) -> std::io::Result<Box<dyn Read>> {
+    let file = File::open(path)?;
+    if is_extension(path, "gz") {
+        Ok(Box::new(GzDecoder::new(file)))
+    } else {
+        Ok(Box::new(file))
+    }
+}
+
+/// Splits `dir/titanic.zip/train.csv` into the archive and the `/`-separated member name.
+fn split_zip_path(path: &Path) -> Option<(PathBuf, String)> {
+    let archive = path
+        .ancestors()
+        .skip(1)
+        .find(|ancestor| is_extension(ancestor, "zip") && ancestor.is_file())?;
+    let member = path
+        .strip_prefix(archive)
+        .ok()?
+        .iter()
+        .map(|component| component.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    Some((archive.to_path_buf(), member))
+}
+
+fn read_zip_member(archive: &Path, member: &str) -> std::result::Result<Vec<u8>, String> {
+    let file = File::open(archive).map_err(|e| e.to_string())?;
+    let mut archive = ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let name = archive
+        .file_names()
+        .find(|name| *name == member || name.ends_with(&format!("/{}", member)))
+        .map(str::to_string)
+        .ok_or_else(|| format!("{} is not in the archive", member))?;
+    let mut zip_file = archive.by_name(&name).map_err(|e| e.to_string())?;
+    let mut buf = vec![];
+    zip_file.read_to_end(&mut buf).map_err(|e| e.to_string())?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use std::io::Write;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use rstest::*;
+    use zip::write::FileOptions;
+    use zip::ZipWriter;
+
+    use crate::test_utils::temp_path;
+
+    const CSV: &str = "PassengerId,Survived\n1,0\n2,1\n";
+
+    /// A directory of its own for each test, as `train.csv.gz` is read in place of `train.csv`.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = temp_path("source", name);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn read_all(path: &Path) -> Result<Vec<Vec<String>>> {
+        let mut rdr = open_csv(path)?;
+        Ok(rdr
+            .records()
+            .map(|r| r.unwrap().iter().map(str::to_string).collect())
+            .collect())
+    }
+
+    fn expected() -> Vec<Vec<String>> {
+        vec![
+            vec!["1".to_string(), "0".to_string()],
+            vec!["2".to_string(), "1".to_string()],
+        ]
+    }
+
+    #[rstest]
+    fn test_open_csv_gz() {
+        let dir = temp_dir("gz");
+        let mut encoder = GzEncoder::new(
+            File::create(dir.join("train.csv.gz")).unwrap(),
+            Compression::default(),
+        );
+        encoder.write_all(CSV.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        assert_eq!(read_all(&dir.join("train.csv.gz")).unwrap(), expected());
+        assert_eq!(read_all(&dir.join("train.csv")).unwrap(), expected());
+    }
+
+    #[rstest]
+    #[case("train.csv")]
+    #[case("titanic/train.csv")]
+    fn test_open_csv_zip(#[case] member: &str) {
+        let dir = temp_dir(&member.replace('/', "-"));
+        let archive = dir.join("titanic.zip");
+        let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+        zip.start_file(member, FileOptions::default()).unwrap();
+        zip.write_all(CSV.as_bytes()).unwrap();
+        zip.finish().unwrap();
+
+        assert_eq!(read_all(&archive.join("train.csv")).unwrap(), expected());
+        assert!(matches!(
+            read_all(&archive.join("test.csv")),
+            Err(LoaderError::Read { .. })
+        ));
+    }
+
+    #[rstest]
+    fn test_open_csv_missing() {
+        let path = temp_dir("missing").join("train.csv");
+        assert_eq!(
+            read_all(&path).err().unwrap(),
+            LoaderError::Read {
+                path,
+                message: "no such file".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,71 @@
+/// A ticket such as `A/5 21171`, `PC 17599` or `113803`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ticket {
+    /// The ticket as written in the CSV.
+    pub full: String,
+    /// Upper-cased prefix without dots, slashes and spaces, e.g. `A5` for `A./5.`.
+    pub prefix: Option<String>,
+    pub number: Option<u64>,
+}
+
+impl Ticket {
+    /// The last token is the number if it is numeric; everything before it is the prefix.
+    pub fn parse(full: &str) -> Self {
+        let tokens = full.split_whitespace().collect::<Vec<_>>();
+        let (prefix, number) = match tokens.split_last() {
+            Some((last, rest)) => match last.parse::<u64>() {
+                Ok(number) => (rest, Some(number)),
+                Err(_) => (&tokens[..], None),
+            },
+            None => (&tokens[..], None),
+        };
+        let prefix = prefix
+            .concat()
+            .chars()
+            .filter(|c| !matches!(c, '.' | '/'))
+            .collect::<String>()
+            .to_uppercase();
+        Self {
+            full: full.to_string(),
+            prefix: if prefix.is_empty() {
+                None
+            } else {
+                Some(prefix)
+            },
+            number,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    #[rstest]
+    #[case("A/5 21171", Some("A5"), Some(21171))]
+    #[case("A./5. 2152", Some("A5"), Some(2152))]
+    #[case("PC 17599", Some("PC"), Some(17599))]
+    #[case("STON/O2. 3101282", Some("STONO2"), Some(3101282))]
+    #[case("STON/O 2. 3101294", Some("STONO2"), Some(3101294))]
+    #[case("SOTON/O.Q. 3101307", Some("SOTONOQ"), Some(3101307))]
+    #[case("113803", None, Some(113803))]
+    #[case("LINE", Some("LINE"), None)]
+    fn test_Ticket_parse(
+        #[case] full: &str,
+        #[case] prefix: Option<&str>,
+        #[case] number: Option<u64>,
+    ) {
+        let actual = Ticket::parse(full);
+        assert_eq!(
+            actual,
+            Ticket {
+                full: full.to_string(),
+                prefix: prefix.map(str::to_string),
+                number,
+            }
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use serde_json;
+
+use crate::config::ModelConfig;
+use crate::feature::FeatureMatrix;
+
+#[cfg(feature = "lightgbm")]
+mod lightgbm;
+#[cfg(feature = "xgboost")]
+mod xgboost;
+
+#[cfg(feature = "lightgbm")]
+pub use self::lightgbm::LightGBMModel;
+#[cfg(feature = "xgboost")]
+pub use self::xgboost::XGBoostModel;
+
+pub trait AbstractModel {
+    /// Trains on `train_features`; `valid` is only used for evaluation by backends that support it.
+    fn train(
+        &mut self,
+        train_features: &FeatureMatrix,
+        train_label: &[f32],
+        valid: Option<(&FeatureMatrix, &[f32])>,
+        sample_weight: Option<&[f32]>,
+        params: &serde_json::Value,
+    ) -> Result<()>;
+    fn predict(&self, features: &FeatureMatrix) -> Result<Vec<f64>>;
+    fn save(&self, path: &str) -> Result<()>;
+    fn load(&mut self, path: &str) -> Result<()>;
+    /// Whether `train` and `predict` accept missing values; see [`check_missing`].
+    fn handles_missing(&self) -> bool {
+        false
+    }
+    /// Returns the model as a GBDT model when it can report feature importances.
+    fn as_gbdt(&self) -> Option<&dyn AbstractGBDTModel> {
+        None
+    }
+}
+
+pub trait AbstractGBDTModel: AbstractModel {
+    fn feature_names(&self) -> Result<Option<Vec<String>>>;
+    fn feature_importances(&self) -> Result<Vec<f64>>;
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelType {
+    #[serde(alias = "lgb", alias = "lgbm")]
+    LightGBM,
+    #[serde(alias = "xgb")]
+    XGBoost,
+}
+
+impl ModelType {
+    /// Name of the cargo feature that compiles the backend in.
+    pub fn feature_name(&self) -> &'static str {
+        match self {
+            ModelType::LightGBM => "lightgbm",
+            ModelType::XGBoost => "xgboost",
+        }
+    }
+}
+
+impl fmt::Display for ModelType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.feature_name())
+    }
+}
+
+impl FromStr for ModelType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "lightgbm" | "lgb" | "lgbm" => Ok(ModelType::LightGBM),
+            "xgboost" | "xgb" => Ok(ModelType::XGBoost),
+            _ => Err(anyhow!("unknown model type: {}", s)),
+        }
+    }
+}
+
+/// Builds the backend selected by `config.model_type`, failing if its cargo feature is disabled.
+pub fn build_model(config: &ModelConfig) -> Result<Box<dyn AbstractGBDTModel>> {
+    match config.model_type {
+        #[cfg(feature = "lightgbm")]
+        ModelType::LightGBM => {
+            let mut model = LightGBMModel::new();
+            model.set_native_categorical(config.native_categorical);
+            Ok(Box::new(model))
+        }
+        #[cfg(feature = "xgboost")]
+        ModelType::XGBoost => {
+            let mut model = XGBoostModel::new();
+            model.set_native_categorical(config.native_categorical);
+            Ok(Box::new(model))
+        }
+        #[allow(unreachable_patterns)]
+        model_type => Err(anyhow!(
+            "{} backend is not enabled; rebuild with `--features {}`",
+            model_type,
+            model_type.feature_name()
+        )),
+    }
+}
+
+/// Fails if `features` has missing values and `model` cannot handle them.
+pub fn check_missing<M: AbstractModel + ?Sized>(model: &M, features: &FeatureMatrix) -> Result<()> {
+    if model.handles_missing() {
+        return Ok(());
+    }
+    let missing = features.missing_features();
+    if !missing.is_empty() {
+        bail!(
+            "features {:?} have missing values, which the model cannot handle; \
+             impute them in [features.impute] or remove them from features.names",
+            missing
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    #[rstest]
+    #[case("lightgbm", Some(ModelType::LightGBM))]
+    #[case("LightGBM", Some(ModelType::LightGBM))]
+    #[case("xgb", Some(ModelType::XGBoost))]
+    #[case("catboost", None)]
+    fn test_ModelType_from_str(#[case] s: &str, #[case] expected: Option<ModelType>) {
+        let actual = s.parse::<ModelType>().ok();
+        assert_eq!(actual, expected);
+    }
+
+    #[cfg(not(feature = "xgboost"))]
+    #[rstest]
+    fn test_build_model_disabled_backend() {
+        let config = ModelConfig {
+            model_type: ModelType::XGBoost,
+            native_categorical: false,
+            params: serde_json::json!({}),
+        };
+        let err = build_model(&config).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "xgboost backend is not enabled; rebuild with `--features xgboost`"
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use ::lightgbm as lgb;
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::feature::{FeatureMatrix, FeatureSchema};
+use crate::model::{AbstractGBDTModel, AbstractModel};
+
+pub struct LightGBMModel {
+    booster: Option<lgb::Booster>,
+    feature_names: Option<Vec<String>>,
+    native_categorical: bool,
+}
+
+impl LightGBMModel {
+    pub fn new() -> Self {
+        Self {
+            booster: None,
+            feature_names: None,
+            native_categorical: false,
+        }
+    }
+
+    /// Whether the categorical features of the schema are passed as `categorical_feature`;
+    /// off by default, which splits on their codes as numbers.
+    pub fn set_native_categorical(&mut self, native_categorical: bool) {
+        self.native_categorical = native_categorical;
+    }
+
+    /// `params` with the categorical features of `schema` as `categorical_feature`, if enabled.
+    fn train_params(
+        &self,
+        schema: &FeatureSchema,
+        params: &serde_json::Value,
+    ) -> serde_json::Value {
+        let mut params = params.clone();
+        let categorical_indices = schema.categorical_indices();
+        if self.native_categorical && !categorical_indices.is_empty() {
+            let indices = categorical_indices
+                .iter()
+                .map(|j| j.to_string())
+                .collect::<Vec<_>>();
+            params["categorical_feature"] = serde_json::Value::from(indices.join(","));
+        }
+        params
+    }
+
+    fn booster(&self) -> Result<&lgb::Booster> {
+        self.booster
+            .as_ref()
+            .ok_or_else(|| anyhow!("the LightGBM model has not been trained or loaded"))
+    }
+}
+
+impl Default for LightGBMModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AbstractModel for LightGBMModel {
+    fn train(
+        &mut self,
+        train_features: &FeatureMatrix,
+        train_label: &[f32],
+        _valid: Option<(&FeatureMatrix, &[f32])>,
+        sample_weight: Option<&[f32]>,
+        params: &serde_json::Value,
+    ) -> Result<()> {
+        // lightgbm-rs only builds datasets from features and labels
+        if sample_weight.is_some() {
+            bail!("LightGBM backend does not support sample weights");
+        }
+        if train_features.n_rows() == 0 {
+            bail!("cannot train LightGBM without rows");
+        }
+        let params = self.train_params(train_features.schema(), params);
+        let dataset = lgb::Dataset::from_mat(to_rows(train_features), train_label.to_vec())
+            .context("failed to build the LightGBM dataset")?;
+        let booster = lgb::Booster::train(dataset, &params).context("failed to train LightGBM")?;
+        self.booster = Some(booster);
+
+        self.feature_names = Some(
+            train_features
+                .schema()
+                .names()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        );
+        Ok(())
+    }
+
+    fn predict(&self, features: &FeatureMatrix) -> Result<Vec<f64>> {
+        let booster = self.booster()?;
+        if features.n_rows() == 0 {
+            return Ok(vec![]);
+        }
+        let result = booster.predict(to_rows(features))?;
+        result
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("LightGBM returned no predictions"))
+    }
+
+    fn save(&self, path: &str) -> Result<()> {
+        self.booster()?
+            .save_file(path)
+            .with_context(|| format!("failed to save the LightGBM model to {}", path))
+    }
+
+    fn load(&mut self, path: &str) -> Result<()> {
+        self.booster = Some(lgb::Booster::from_file(path)?);
+        Ok(())
+    }
+
+    /// NaN is LightGBM's default missing value.
+    fn handles_missing(&self) -> bool {
+        true
+    }
+
+    fn as_gbdt(&self) -> Option<&dyn AbstractGBDTModel> {
+        Some(self)
+    }
+}
+
+impl AbstractGBDTModel for LightGBMModel {
+    fn feature_names(&self) -> Result<Option<Vec<String>>> {
+        Ok(self.feature_names.clone())
+    }
+
+    fn feature_importances(&self) -> Result<Vec<f64>> {
+        Ok(self.booster()?.feature_importance()?)
+    }
+}
+
+/// lightgbm-rs only takes owned rows, so this is the one copy of the features it needs.
+fn to_rows(features: &FeatureMatrix) -> Vec<Vec<f64>> {
+    features.rows().map(<[f64]>::to_vec).collect()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    use crate::feature::FeatureField;
+
+    #[rstest]
+    #[case(false, vec![FeatureField::categorical("pclass")], None)]
+    #[case(true, vec![FeatureField::float("age")], None)]
+    #[case(
+        true,
+        vec![
+            FeatureField::categorical("pclass"),
+            FeatureField::float("age"),
+            FeatureField::categorical("title"),
+        ],
+        Some("0,2")
+    )]
+    fn test_LightGBMModel_train_params(
+        #[case] native_categorical: bool,
+        #[case] fields: Vec<FeatureField>,
+        #[case] expected: Option<&str>,
+    ) {
+        let mut model = LightGBMModel::new();
+        model.set_native_categorical(native_categorical);
+        let schema = FeatureSchema::new(fields).unwrap();
+        let params = serde_json::json!({ "objective": "binary" });
+        let actual = model.train_params(&schema, &params);
+        assert_eq!(actual["objective"], "binary");
+        assert_eq!(
+            actual.get("categorical_feature").and_then(|v| v.as_str()),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn test_LightGBMModel_untrained() {
+        let model = LightGBMModel::new();
+        let features = FeatureMatrix::empty(1);
+        let expected = "the LightGBM model has not been trained or loaded";
+        assert_eq!(
+            model.predict(&features).err().unwrap().to_string(),
+            expected
+        );
+        assert_eq!(model.save("model.txt").err().unwrap().to_string(), expected);
+        assert_eq!(
+            model.feature_importances().err().unwrap().to_string(),
+            expected
+        );
+    }
+}
@@ -0,0 +1,390 @@
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::feature::{FeatureMatrix, FeatureSchema};
+use crate::model::{AbstractGBDTModel, AbstractModel};
+
+mod ffi;
+
+use self::ffi::{Booster, DMatrix};
+
+pub struct XGBoostModel {
+    booster: Option<Booster>,
+    feature_names: Option<Vec<String>>,
+    native_categorical: bool,
+}
+
+impl XGBoostModel {
+    pub fn new() -> Self {
+        Self {
+            booster: None,
+            feature_names: None,
+            native_categorical: false,
+        }
+    }
+
+    /// Whether the categorical features of the schema are given the `c` feature type;
+    /// off by default, which splits on their codes as numbers.
+    pub fn set_native_categorical(&mut self, native_categorical: bool) {
+        self.native_categorical = native_categorical;
+    }
+
+    fn dmatrix(&self, features: &FeatureMatrix) -> Result<DMatrix> {
+        let (indptr, indices, data) = to_csr(features);
+        let mut dmatrix = DMatrix::from_csr(&indptr, &indices, &data, features.n_cols())?;
+        if self.native_categorical {
+            dmatrix.set_feature_types(&feature_types(features.schema()))?;
+        }
+        Ok(dmatrix)
+    }
+
+    fn booster(&self) -> Result<&Booster> {
+        self.booster
+            .as_ref()
+            .ok_or_else(|| anyhow!("the XGBoost model has not been trained or loaded"))
+    }
+}
+
+impl Default for XGBoostModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AbstractModel for XGBoostModel {
+    fn train(
+        &mut self,
+        train_features: &FeatureMatrix,
+        train_label: &[f32],
+        valid: Option<(&FeatureMatrix, &[f32])>,
+        sample_weight: Option<&[f32]>,
+        params: &serde_json::Value,
+    ) -> Result<()> {
+        if train_features.n_rows() == 0 {
+            bail!("cannot train XGBoost without rows");
+        }
+        let mut dtrain = self
+            .dmatrix(train_features)
+            .context("failed to build the XGBoost training matrix")?;
+        dtrain.set_labels(train_label)?;
+        if let Some(sample_weight) = sample_weight {
+            dtrain.set_weights(sample_weight)?;
+        }
+
+        let dvalid = match valid {
+            Some((valid_features, valid_label)) => {
+                let mut dvalid = self
+                    .dmatrix(valid_features)
+                    .context("failed to build the XGBoost validation matrix")?;
+                dvalid.set_labels(valid_label)?;
+                Some(dvalid)
+            }
+            None => None,
+        };
+
+        let num_boost_round = params
+            .get("num_boost_round")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(100) as i32;
+        let mut evaluation_sets = vec![(&dtrain, "train")];
+        if let Some(dvalid) = dvalid.as_ref() {
+            evaluation_sets.push((dvalid, "valid"));
+        }
+        let mut booster = Booster::new(
+            &evaluation_sets
+                .iter()
+                .map(|(dmatrix, _)| *dmatrix)
+                .collect::<Vec<_>>(),
+        )?;
+        for (name, value) in booster_params(params)? {
+            booster
+                .set_param(&name, &value)
+                .with_context(|| format!("invalid XGBoost parameter {}", name))?;
+        }
+        for iteration in 0..num_boost_round {
+            booster
+                .update(iteration, &dtrain)
+                .context("failed to train XGBoost")?;
+            println!("{}", booster.eval(iteration, &evaluation_sets)?);
+        }
+        self.booster = Some(booster);
+
+        self.feature_names = Some(
+            train_features
+                .schema()
+                .names()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        );
+        Ok(())
+    }
+
+    fn predict(&self, features: &FeatureMatrix) -> Result<Vec<f64>> {
+        let booster = self.booster()?;
+        if features.n_rows() == 0 {
+            return Ok(vec![]);
+        }
+        let dmatrix = self
+            .dmatrix(features)
+            .context("failed to build the XGBoost matrix")?;
+
+        let result = booster.predict(&dmatrix)?;
+        Ok(result.iter().map(|v| *v as f64).collect())
+    }
+
+    fn save(&self, path: &str) -> Result<()> {
+        self.booster()?
+            .save(path)
+            .with_context(|| format!("failed to save the XGBoost model to {}", path))
+    }
+
+    fn load(&mut self, path: &str) -> Result<()> {
+        self.booster = Some(
+            Booster::load(path)
+                .with_context(|| format!("failed to load the XGBoost model from {}", path))?,
+        );
+        Ok(())
+    }
+
+    /// Missing values are left out of the matrix; see [`to_csr`].
+    fn handles_missing(&self) -> bool {
+        true
+    }
+
+    fn as_gbdt(&self) -> Option<&dyn AbstractGBDTModel> {
+        Some(self)
+    }
+}
+
+impl AbstractGBDTModel for XGBoostModel {
+    fn feature_names(&self) -> Result<Option<Vec<String>>> {
+        Ok(self.feature_names.clone())
+    }
+
+    /// The number of splits on each feature, like LightGBM's default importance.
+    fn feature_importances(&self) -> Result<Vec<f64>> {
+        let booster = self.booster()?;
+        let mut importances = vec![0.0; booster.n_features()?];
+        for (j, score) in booster.feature_scores("weight")? {
+            if let Some(importance) = importances.get_mut(j) {
+                *importance = f64::from(score);
+            }
+        }
+        Ok(importances)
+    }
+}
+
+/// `features` as a CSR matrix of `(indptr, indices, data)` without its
+/// [`MISSING`](crate::feature::MISSING) values, which is how XGBoost is told they are missing.
+fn to_csr(features: &FeatureMatrix) -> (Vec<usize>, Vec<u32>, Vec<f32>) {
+    let n_cols = features.n_cols();
+    let values = features.values_f32();
+    let mut indptr = Vec::with_capacity(features.n_rows() + 1);
+    let mut indices = Vec::with_capacity(values.len());
+    let mut data = Vec::with_capacity(values.len());
+    indptr.push(0);
+    for i in 0..features.n_rows() {
+        for (j, v) in values[i * n_cols..(i + 1) * n_cols].iter().enumerate() {
+            if !v.is_nan() {
+                indices.push(j as u32);
+                data.push(*v);
+            }
+        }
+        indptr.push(data.len());
+    }
+    (indptr, indices, data)
+}
+
+/// The booster parameters: the defaults below, overridden by every key of `params` except
+/// `num_boost_round`, which sets the number of rounds instead.
+fn booster_params(params: &serde_json::Value) -> Result<Vec<(String, String)>> {
+    let mut booster_params = vec![
+        ("objective".to_string(), "binary:logistic".to_string()),
+        ("verbosity".to_string(), "0".to_string()),
+        ("max_depth".to_string(), "6".to_string()),
+        ("eta".to_string(), "0.1".to_string()),
+    ];
+    for (key, value) in params.as_object().into_iter().flatten() {
+        if key == "num_boost_round" {
+            continue;
+        }
+        let value = match value {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Number(_) | serde_json::Value::Bool(_) => value.to_string(),
+            _ => bail!("model.params.{} must be a string, number or boolean", key),
+        };
+        booster_params.retain(|(name, _)| name != key);
+        booster_params.push((key.clone(), value));
+    }
+    Ok(booster_params)
+}
+
+/// The XGBoost feature type of each column: `c` for the categorical features of `schema`.
+fn feature_types(schema: &FeatureSchema) -> Vec<&'static str> {
+    schema
+        .fields()
+        .iter()
+        .map(|field| if field.categorical { "c" } else { "q" })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    use crate::feature::{FeatureField, FeatureSchema, MISSING};
+
+    fn features(rows: &[[f64; 2]]) -> FeatureMatrix {
+        let schema = FeatureSchema::new(vec![
+            FeatureField::float("age"),
+            FeatureField::float("fare"),
+        ])
+        .unwrap();
+        let mut features = FeatureMatrix::new(schema);
+        for row in rows.iter() {
+            features.push_row(row);
+        }
+        features
+    }
+
+    #[rstest]
+    fn test_to_csr() {
+        let actual = to_csr(&features(&[
+            [22.0, MISSING],
+            [MISSING, MISSING],
+            [0.0, 7.25],
+        ]));
+        assert_eq!(
+            actual,
+            (vec![0, 1, 1, 3], vec![0, 0, 1], vec![22.0, 0.0, 7.25])
+        );
+    }
+
+    #[rstest]
+    fn test_booster_params() {
+        let actual = booster_params(&serde_json::json!({
+            "num_boost_round": 10,
+            "eta": 0.05,
+            "subsample": 0.8,
+            "eval_metric": "auc",
+            "validate_parameters": true,
+        }))
+        .unwrap();
+        let expected = [
+            ("objective", "binary:logistic"),
+            ("verbosity", "0"),
+            ("max_depth", "6"),
+            ("eta", "0.05"),
+            ("eval_metric", "auc"),
+            ("subsample", "0.8"),
+            ("validate_parameters", "true"),
+        ]
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    fn test_booster_params_invalid() {
+        let actual = booster_params(&serde_json::json!({ "monotone_constraints": [1, 0] }))
+            .err()
+            .unwrap();
+        assert_eq!(
+            actual.to_string(),
+            "model.params.monotone_constraints must be a string, number or boolean"
+        );
+    }
+
+    #[rstest]
+    fn test_XGBoostModel_train_missing() {
+        let features = features(&[
+            [22.0, MISSING],
+            [38.0, 71.28],
+            [MISSING, 7.92],
+            [35.0, 53.1],
+        ]);
+        let mut model = XGBoostModel::new();
+        assert!(model.handles_missing());
+        model
+            .train(
+                &features,
+                &[0.0, 1.0, 1.0, 0.0],
+                Some((&features, &[0.0, 1.0, 1.0, 0.0])),
+                None,
+                &serde_json::json!({ "num_boost_round": 2 }),
+            )
+            .unwrap();
+        let actual = model.predict(&features).unwrap();
+        assert_eq!(actual.len(), 4);
+        assert!(actual.iter().all(|p| (0.0..=1.0).contains(p)));
+        assert!(model.predict(&FeatureMatrix::empty(0)).unwrap().is_empty());
+    }
+
+    #[rstest]
+    fn test_feature_types() {
+        let schema = FeatureSchema::new(vec![
+            FeatureField::categorical("pclass"),
+            FeatureField::float("age"),
+            FeatureField::categorical("title"),
+        ])
+        .unwrap();
+        assert_eq!(feature_types(&schema), vec!["c", "q", "c"]);
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn test_XGBoostModel_train_native_categorical(#[case] native_categorical: bool) {
+        let schema = FeatureSchema::new(vec![
+            FeatureField::categorical("pclass"),
+            FeatureField::float("age"),
+        ])
+        .unwrap();
+        let mut features = FeatureMatrix::new(schema);
+        for row in [[0.0, 22.0], [2.0, 38.0], [1.0, MISSING], [0.0, 35.0]].iter() {
+            features.push_row(row);
+        }
+        let mut model = XGBoostModel::new();
+        model.set_native_categorical(native_categorical);
+        model
+            .train(
+                &features,
+                &[0.0, 1.0, 1.0, 0.0],
+                None,
+                None,
+                &serde_json::json!({ "num_boost_round": 2 }),
+            )
+            .unwrap();
+        assert_eq!(model.predict(&features).unwrap().len(), 4);
+    }
+
+    #[rstest]
+    fn test_XGBoostModel_train_without_rows() {
+        let mut model = XGBoostModel::new();
+        let actual = model
+            .train(&features(&[]), &[], None, None, &serde_json::json!({}))
+            .err()
+            .unwrap();
+        assert_eq!(actual.to_string(), "cannot train XGBoost without rows");
+    }
+
+    #[rstest]
+    fn test_XGBoostModel_untrained() {
+        let model = XGBoostModel::new();
+        let features = FeatureMatrix::empty(1);
+        let expected = "the XGBoost model has not been trained or loaded";
+        assert_eq!(
+            model.predict(&features).err().unwrap().to_string(),
+            expected
+        );
+        assert_eq!(model.save("model.bin").err().unwrap().to_string(), expected);
+        assert_eq!(
+            model.feature_importances().err().unwrap().to_string(),
+            expected
+        );
+    }
+}
@@ -0,0 +1,264 @@
+//! Safe wrappers of the parts of the XGBoost C API the backend uses.
+
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+use std::slice;
+
+use anyhow::{anyhow, Result};
+use xgboost_lib_sys as sys;
+
+/// Turns the return code of a C API call into the last XGBoost error.
+fn check(code: c_int) -> Result<()> {
+    if code == 0 {
+        return Ok(());
+    }
+    // SAFETY: XGBGetLastError returns a NUL-terminated, thread-local message.
+    let message = unsafe { CStr::from_ptr(sys::XGBGetLastError()) };
+    Err(anyhow!("{}", message.to_string_lossy()))
+}
+
+fn c_string(s: &str) -> Result<CString> {
+    CString::new(s).map_err(|_| anyhow!("{:?} contains a NUL byte", s))
+}
+
+/// Copies `len` values XGBoost owns until the next call on the same handle.
+///
+/// # Safety
+///
+/// `data` must point to `len` values, or `len` must be 0.
+unsafe fn copy_slice<T: Copy>(data: *const T, len: usize) -> Vec<T> {
+    if len == 0 {
+        return Vec::new();
+    }
+    slice::from_raw_parts(data, len).to_vec()
+}
+
+pub struct DMatrix {
+    handle: sys::DMatrixHandle,
+}
+
+impl DMatrix {
+    /// A matrix of `n_cols` columns from CSR arrays; entries left out of them are missing.
+    pub fn from_csr(
+        indptr: &[usize],
+        indices: &[u32],
+        data: &[f32],
+        n_cols: usize,
+    ) -> Result<Self> {
+        let mut handle = ptr::null_mut();
+        // SAFETY: the arrays outlive the call, which copies them.
+        check(unsafe {
+            sys::XGDMatrixCreateFromCSREx(
+                indptr.as_ptr(),
+                indices.as_ptr(),
+                data.as_ptr(),
+                indptr.len(),
+                data.len(),
+                n_cols,
+                &mut handle,
+            )
+        })?;
+        Ok(Self { handle })
+    }
+
+    pub fn set_labels(&mut self, labels: &[f32]) -> Result<()> {
+        self.set_float_info("label", labels)
+    }
+
+    pub fn set_weights(&mut self, weights: &[f32]) -> Result<()> {
+        self.set_float_info("weight", weights)
+    }
+
+    /// Sets the type of each column: `q` for quantitative and `c` for categorical.
+    pub fn set_feature_types(&mut self, feature_types: &[&str]) -> Result<()> {
+        let field = c_string("feature_type")?;
+        let feature_types = feature_types
+            .iter()
+            .map(|t| c_string(t))
+            .collect::<Result<Vec<_>>>()?;
+        let mut type_ptrs = feature_types.iter().map(|t| t.as_ptr()).collect::<Vec<_>>();
+        // SAFETY: the strings outlive the call, which copies them.
+        check(unsafe {
+            sys::XGDMatrixSetStrFeatureInfo(
+                self.handle,
+                field.as_ptr(),
+                type_ptrs.as_mut_ptr(),
+                type_ptrs.len() as sys::bst_ulong,
+            )
+        })
+    }
+
+    fn set_float_info(&mut self, field: &str, values: &[f32]) -> Result<()> {
+        let field = c_string(field)?;
+        // SAFETY: `values` outlives the call, which copies it.
+        check(unsafe {
+            sys::XGDMatrixSetFloatInfo(
+                self.handle,
+                field.as_ptr(),
+                values.as_ptr(),
+                values.len() as sys::bst_ulong,
+            )
+        })
+    }
+}
+
+impl Drop for DMatrix {
+    fn drop(&mut self) {
+        // SAFETY: the handle was created by XGDMatrixCreateFromCSREx and is freed once.
+        unsafe { sys::XGDMatrixFree(self.handle) };
+    }
+}
+
+pub struct Booster {
+    handle: sys::BoosterHandle,
+}
+
+impl Booster {
+    /// An untrained booster that caches `dmatrices`.
+    pub fn new(dmatrices: &[&DMatrix]) -> Result<Self> {
+        let handles = dmatrices.iter().map(|d| d.handle).collect::<Vec<_>>();
+        let mut handle = ptr::null_mut();
+        // SAFETY: the handles are valid for the call.
+        check(unsafe {
+            sys::XGBoosterCreate(
+                handles.as_ptr(),
+                handles.len() as sys::bst_ulong,
+                &mut handle,
+            )
+        })?;
+        Ok(Self { handle })
+    }
+
+    /// Loads a model saved by [`Booster::save`].
+    pub fn load(path: &str) -> Result<Self> {
+        let booster = Self::new(&[])?;
+        let path = c_string(path)?;
+        // SAFETY: the handle and path are valid for the call.
+        check(unsafe { sys::XGBoosterLoadModel(booster.handle, path.as_ptr()) })?;
+        Ok(booster)
+    }
+
+    /// Saves the model; XGBoost picks the format by extension and defaults to UBJSON.
+    pub fn save(&self, path: &str) -> Result<()> {
+        let path = c_string(path)?;
+        // SAFETY: the handle and path are valid for the call.
+        check(unsafe { sys::XGBoosterSaveModel(self.handle, path.as_ptr()) })
+    }
+
+    pub fn set_param(&mut self, name: &str, value: &str) -> Result<()> {
+        let (name, value) = (c_string(name)?, c_string(value)?);
+        // SAFETY: the handle and strings are valid for the call.
+        check(unsafe { sys::XGBoosterSetParam(self.handle, name.as_ptr(), value.as_ptr()) })
+    }
+
+    /// Runs boosting round `iteration` on `dtrain`.
+    pub fn update(&mut self, iteration: i32, dtrain: &DMatrix) -> Result<()> {
+        // SAFETY: both handles are valid for the call.
+        check(unsafe { sys::XGBoosterUpdateOneIter(self.handle, iteration, dtrain.handle) })
+    }
+
+    /// The metrics of round `iteration` on the named matrices, e.g. `[0]\ttrain-logloss:0.61`.
+    pub fn eval(&self, iteration: i32, dmatrices: &[(&DMatrix, &str)]) -> Result<String> {
+        let mut handles = dmatrices.iter().map(|(d, _)| d.handle).collect::<Vec<_>>();
+        let names = dmatrices
+            .iter()
+            .map(|(_, name)| c_string(name))
+            .collect::<Result<Vec<_>>>()?;
+        let mut name_ptrs = names.iter().map(|name| name.as_ptr()).collect::<Vec<_>>();
+        let mut out: *const c_char = ptr::null();
+        // SAFETY: the handles and names are valid for the call, and `out` is a NUL-terminated
+        // string XGBoost owns until the next call.
+        unsafe {
+            check(sys::XGBoosterEvalOneIter(
+                self.handle,
+                iteration,
+                handles.as_mut_ptr(),
+                name_ptrs.as_mut_ptr(),
+                handles.len() as sys::bst_ulong,
+                &mut out,
+            ))?;
+            Ok(CStr::from_ptr(out).to_string_lossy().into_owned())
+        }
+    }
+
+    /// The predictions of all rounds for the rows of `dmatrix`.
+    pub fn predict(&self, dmatrix: &DMatrix) -> Result<Vec<f32>> {
+        let config = c_string(
+            r#"{"type": 0, "training": false, "iteration_begin": 0, "iteration_end": 0, "strict_shape": false}"#,
+        )?;
+        let mut shape: *const sys::bst_ulong = ptr::null();
+        let mut dim: sys::bst_ulong = 0;
+        let mut result: *const f32 = ptr::null();
+        // SAFETY: the handles and config are valid for the call, and the shape and result
+        // arrays XGBoost owns are copied before the next call.
+        unsafe {
+            check(sys::XGBoosterPredictFromDMatrix(
+                self.handle,
+                dmatrix.handle,
+                config.as_ptr(),
+                &mut shape,
+                &mut dim,
+                &mut result,
+            ))?;
+            let shape = copy_slice(shape, dim as usize);
+            Ok(copy_slice(result, shape.iter().product::<u64>() as usize))
+        }
+    }
+
+    /// The number of features the model was trained on.
+    pub fn n_features(&self) -> Result<usize> {
+        let mut n_features: sys::bst_ulong = 0;
+        // SAFETY: the handle is valid for the call.
+        check(unsafe { sys::XGBoosterGetNumFeature(self.handle, &mut n_features) })?;
+        Ok(n_features as usize)
+    }
+
+    /// The `importance_type` scores of the features used by the trees, by the index `j` of
+    /// their default `f{j}` names; unused features are left out.
+    pub fn feature_scores(&self, importance_type: &str) -> Result<Vec<(usize, f32)>> {
+        let config =
+            c_string(&serde_json::json!({ "importance_type": importance_type }).to_string())?;
+        let mut n_features: sys::bst_ulong = 0;
+        let mut features: *mut *const c_char = ptr::null_mut();
+        let mut dim: sys::bst_ulong = 0;
+        let mut shape: *const sys::bst_ulong = ptr::null();
+        let mut scores: *const f32 = ptr::null();
+        // SAFETY: the handle and config are valid for the call, and the names and scores
+        // XGBoost owns are copied before the next call.
+        let (names, scores) = unsafe {
+            check(sys::XGBoosterFeatureScore(
+                self.handle,
+                config.as_ptr(),
+                &mut n_features,
+                &mut features,
+                &mut dim,
+                &mut shape,
+                &mut scores,
+            ))?;
+            let names = copy_slice(features as *const *const c_char, n_features as usize)
+                .into_iter()
+                .map(|name| CStr::from_ptr(name).to_string_lossy().into_owned())
+                .collect::<Vec<_>>();
+            (names, copy_slice(scores, n_features as usize))
+        };
+        names
+            .iter()
+            .zip(scores)
+            .map(|(name, score)| {
+                let j = name
+                    .strip_prefix('f')
+                    .and_then(|j| j.parse().ok())
+                    .ok_or_else(|| anyhow!("unexpected XGBoost feature name {:?}", name))?;
+                Ok((j, score))
+            })
+            .collect()
+    }
+}
+
+impl Drop for Booster {
+    fn drop(&mut self) {
+        // SAFETY: the handle was created by XGBoosterCreate and is freed once.
+        unsafe { sys::XGBoosterFree(self.handle) };
+    }
+}
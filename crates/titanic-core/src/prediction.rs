@@ -0,0 +1,128 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use csv::{Reader, Writer};
+
+/// Per-fold predictions stored by `train` and `predict`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredPredictions {
+    pub passenger_ids: Vec<u32>,
+    /// One vector of probabilities per fold model, aligned with `passenger_ids`.
+    pub predictions: Vec<Vec<f64>>,
+}
+
+impl StoredPredictions {
+    pub fn new(passenger_ids: Vec<u32>, predictions: Vec<Vec<f64>>) -> Result<Self> {
+        for (i, prediction) in predictions.iter().enumerate() {
+            if prediction.len() != passenger_ids.len() {
+                bail!(
+                    "fold{} has {} predictions but there are {} passengers",
+                    i + 1,
+                    prediction.len(),
+                    passenger_ids.len()
+                );
+            }
+        }
+        Ok(Self {
+            passenger_ids,
+            predictions,
+        })
+    }
+
+    /// Averages the fold predictions of each passenger.
+    pub fn mean(&self) -> Vec<f64> {
+        (0..self.passenger_ids.len())
+            .map(|i| {
+                self.predictions.iter().map(|p| p[i]).sum::<f64>() / self.predictions.len() as f64
+            })
+            .collect()
+    }
+
+    /// Writes `PassengerId,fold1,...,foldN` rows.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut wtr = Writer::from_path(path)
+            .with_context(|| format!("failed to create {}", path.display()))?;
+        let mut header = vec!["PassengerId".to_string()];
+        header.extend((1..=self.predictions.len()).map(|fold| format!("fold{}", fold)));
+        wtr.write_record(&header)?;
+        for (i, passenger_id) in self.passenger_ids.iter().enumerate() {
+            let mut record = vec![passenger_id.to_string()];
+            record.extend(self.predictions.iter().map(|p| p[i].to_string()));
+            wtr.write_record(&record)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let mut rdr = Reader::from_path(path)
+            .with_context(|| format!("failed to open predictions {}", path.display()))?;
+        let n_folds = rdr.headers()?.len().saturating_sub(1);
+        if n_folds == 0 {
+            bail!("{} has no fold columns", path.display());
+        }
+        let mut passenger_ids = vec![];
+        let mut predictions = vec![vec![]; n_folds];
+        for (i, record) in rdr.records().enumerate() {
+            let record = record?;
+            let line = i + 2;
+            passenger_ids.push(record[0].parse::<u32>().map_err(|_| {
+                anyhow!(
+                    "{}:{}: invalid PassengerId {:?}",
+                    path.display(),
+                    line,
+                    &record[0]
+                )
+            })?);
+            for (fold, prediction) in predictions.iter_mut().enumerate() {
+                let v = &record[fold + 1];
+                prediction.push(v.parse::<f64>().map_err(|_| {
+                    anyhow!(
+                        "{}:{}: invalid prediction {:?} for fold{}",
+                        path.display(),
+                        line,
+                        v,
+                        fold + 1
+                    )
+                })?);
+            }
+        }
+        Self::new(passenger_ids, predictions)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    use crate::test_utils::temp_path;
+
+    #[rstest]
+    fn test_StoredPredictions_save_load() {
+        let path = temp_path("prediction", "test.csv");
+        let expected =
+            StoredPredictions::new(vec![892, 893], vec![vec![0.25, 0.5], vec![0.75, 1.0]]).unwrap();
+        expected.save(&path).unwrap();
+        let actual = StoredPredictions::load(&path).unwrap();
+        assert_eq!(actual, expected);
+        assert_eq!(actual.mean(), vec![0.5, 0.75]);
+    }
+
+    #[rstest]
+    fn test_StoredPredictions_new_length_mismatch() {
+        let actual = StoredPredictions::new(vec![892, 893], vec![vec![0.25]]);
+        assert_eq!(
+            actual.err().unwrap().to_string(),
+            "fold1 has 1 predictions but there are 2 passengers"
+        );
+    }
+}
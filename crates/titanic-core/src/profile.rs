@@ -0,0 +1,482 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::feature::{Column, CATEGORICAL_COLUMNS, NUMERIC_COLUMNS};
+use crate::loader::InputData;
+use crate::utils::quantile;
+
+/// One or two columns to compute survival rates by, written as `sex` or `pclass,sex`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GroupBy(pub Vec<Column>);
+
+impl FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let columns = s
+            .split(',')
+            .map(Column::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        if columns.is_empty() || columns.len() > 2 {
+            return Err(format!("expected one or two columns, got {:?}", s));
+        }
+        Ok(GroupBy(columns))
+    }
+}
+
+impl GroupBy {
+    /// `sex`, `pclass`, `embarked`, `title` and `pclass,sex`.
+    pub fn defaults() -> Vec<GroupBy> {
+        vec![
+            GroupBy(vec![Column::Sex]),
+            GroupBy(vec![Column::Pclass]),
+            GroupBy(vec![Column::Embarked]),
+            GroupBy(vec![Column::Title]),
+            GroupBy(vec![Column::Pclass, Column::Sex]),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NumericSummary {
+    pub column: Column,
+    pub count: usize,
+    pub missing: usize,
+    pub mean: Option<f64>,
+    /// Sample standard deviation.
+    pub std: Option<f64>,
+    pub min: Option<f64>,
+    pub q25: Option<f64>,
+    pub median: Option<f64>,
+    pub q75: Option<f64>,
+    pub max: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValueCount {
+    /// `None` counts the missing values.
+    pub value: Option<String>,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValueCounts {
+    pub column: Column,
+    /// Sorted by value, missing last.
+    pub counts: Vec<ValueCount>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GroupRate {
+    /// One value per grouped column; `None` for missing.
+    pub key: Vec<Option<String>>,
+    pub count: usize,
+    pub survived: usize,
+    pub rate: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SurvivalRates {
+    pub columns: Vec<Column>,
+    pub groups: Vec<GroupRate>,
+}
+
+/// Profile of one dataset.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatasetProfile {
+    pub name: String,
+    pub rows: usize,
+    pub numeric: Vec<NumericSummary>,
+    pub categorical: Vec<ValueCounts>,
+    /// Empty unless the data has labels.
+    pub survival: Vec<SurvivalRates>,
+}
+
+/// Share of one categorical value in train and test.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ShareComparison {
+    pub column: Column,
+    pub value: Option<String>,
+    pub train: f64,
+    pub test: f64,
+}
+
+/// Profiles of train and test, with the shares of categorical values side by side.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Profile {
+    pub train: DatasetProfile,
+    pub test: DatasetProfile,
+    pub shares: Vec<ShareComparison>,
+}
+
+impl DatasetProfile {
+    pub fn new(name: &str, data: &[InputData], group_bys: &[GroupBy]) -> Self {
+        let has_labels = !data.is_empty() && data.iter().all(|x| x.survived.is_some());
+        Self {
+            name: name.to_string(),
+            rows: data.len(),
+            numeric: NUMERIC_COLUMNS
+                .iter()
+                .map(|column| summarize(*column, data))
+                .collect(),
+            categorical: CATEGORICAL_COLUMNS
+                .iter()
+                .map(|column| value_counts(*column, data))
+                .collect(),
+            survival: if has_labels {
+                group_bys
+                    .iter()
+                    .map(|group_by| survival_rates(&group_by.0, data))
+                    .collect()
+            } else {
+                vec![]
+            },
+        }
+    }
+}
+
+impl Profile {
+    pub fn new(train: &[InputData], test: &[InputData], group_bys: &[GroupBy]) -> Self {
+        let train = DatasetProfile::new("train", train, group_bys);
+        let test = DatasetProfile::new("test", test, group_bys);
+        let mut shares = vec![];
+        for (train_counts, test_counts) in train.categorical.iter().zip(test.categorical.iter()) {
+            let mut values = train_counts
+                .counts
+                .iter()
+                .chain(test_counts.counts.iter())
+                .map(|c| c.value.clone())
+                .collect::<Vec<_>>();
+            values.sort_by(compare_keys);
+            values.dedup();
+            for value in values {
+                shares.push(ShareComparison {
+                    column: train_counts.column,
+                    train: share(train_counts, &value, train.rows),
+                    test: share(test_counts, &value, test.rows),
+                    value,
+                });
+            }
+        }
+        Self {
+            train,
+            test,
+            shares,
+        }
+    }
+}
+
+fn share(counts: &ValueCounts, value: &Option<String>, rows: usize) -> f64 {
+    let count = counts
+        .counts
+        .iter()
+        .find(|c| &c.value == value)
+        .map_or(0, |c| c.count);
+    if rows == 0 {
+        0.0
+    } else {
+        count as f64 / rows as f64
+    }
+}
+
+/// Missing values sort last.
+fn compare_keys(a: &Option<String>, b: &Option<String>) -> std::cmp::Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => match (a.parse::<f64>(), b.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.total_cmp(&b),
+            _ => a.cmp(b),
+        },
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    }
+}
+
+fn summarize(column: Column, data: &[InputData]) -> NumericSummary {
+    let mut values = data
+        .iter()
+        .filter_map(|x| column.numeric(x))
+        .collect::<Vec<_>>();
+    values.sort_by(f64::total_cmp);
+    let count = values.len();
+    let mean = (count > 0).then(|| values.iter().sum::<f64>() / count as f64);
+    let std = mean.filter(|_| count > 1).map(|mean| {
+        (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (count - 1) as f64).sqrt()
+    });
+    NumericSummary {
+        column,
+        count,
+        missing: data.len() - count,
+        mean,
+        std,
+        min: values.first().copied(),
+        q25: quantile(&values, 0.25),
+        median: quantile(&values, 0.5),
+        q75: quantile(&values, 0.75),
+        max: values.last().copied(),
+    }
+}
+
+fn value_counts(column: Column, data: &[InputData]) -> ValueCounts {
+    let mut counts = BTreeMap::new();
+    for x in data.iter() {
+        *counts.entry(column.key(x)).or_insert(0) += 1;
+    }
+    let mut counts = counts
+        .into_iter()
+        .map(|(value, count)| ValueCount { value, count })
+        .collect::<Vec<_>>();
+    counts.sort_by(|a, b| compare_keys(&a.value, &b.value));
+    ValueCounts { column, counts }
+}
+
+fn survival_rates(columns: &[Column], data: &[InputData]) -> SurvivalRates {
+    let mut groups: BTreeMap<Vec<Option<String>>, (usize, usize)> = BTreeMap::new();
+    for x in data.iter() {
+        let key = columns.iter().map(|column| column.key(x)).collect();
+        let (count, survived) = groups.entry(key).or_default();
+        *count += 1;
+        *survived += x.survived.unwrap_or(0) as usize;
+    }
+    let mut groups = groups
+        .into_iter()
+        .map(|(key, (count, survived))| GroupRate {
+            key,
+            count,
+            survived,
+            rate: survived as f64 / count as f64,
+        })
+        .collect::<Vec<_>>();
+    groups.sort_by(|a, b| {
+        a.key
+            .iter()
+            .zip(b.key.iter())
+            .map(|(a, b)| compare_keys(a, b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    SurvivalRates {
+        columns: columns.to_vec(),
+        groups,
+    }
+}
+
+fn format_value(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("NA")
+}
+
+fn format_number(value: Option<f64>) -> String {
+    value.map_or_else(|| "NA".to_string(), |v| format!("{:.3}", v))
+}
+
+impl fmt::Display for DatasetProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "== {} ({} rows) ==", self.name, self.rows)?;
+        writeln!(
+            f,
+            "{:<10}{:>7}{:>9}{:>10}{:>10}{:>10}{:>10}{:>10}{:>10}{:>10}",
+            "column", "count", "missing", "mean", "std", "min", "25%", "50%", "75%", "max"
+        )?;
+        for s in self.numeric.iter() {
+            writeln!(
+                f,
+                "{:<10}{:>7}{:>9}{:>10}{:>10}{:>10}{:>10}{:>10}{:>10}{:>10}",
+                s.column.to_string(),
+                s.count,
+                s.missing,
+                format_number(s.mean),
+                format_number(s.std),
+                format_number(s.min),
+                format_number(s.q25),
+                format_number(s.median),
+                format_number(s.q75),
+                format_number(s.max)
+            )?;
+        }
+        for counts in self.categorical.iter() {
+            let values = counts
+                .counts
+                .iter()
+                .map(|c| format!("{}={}", format_value(&c.value), c.count))
+                .collect::<Vec<_>>();
+            writeln!(f, "{}: {}", counts.column, values.join(", "))?;
+        }
+        for rates in self.survival.iter() {
+            let columns = rates
+                .columns
+                .iter()
+                .map(Column::to_string)
+                .collect::<Vec<_>>();
+            writeln!(f, "survival rate by {}", columns.join(", "))?;
+            for group in rates.groups.iter() {
+                let key = group.key.iter().map(format_value).collect::<Vec<_>>();
+                writeln!(
+                    f,
+                    "  {:<20}{:>5}/{:<5}{:>7.3}",
+                    key.join(", "),
+                    group.survived,
+                    group.count,
+                    group.rate
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.train)?;
+        write!(f, "{}", self.test)?;
+        writeln!(f, "== train vs test ==")?;
+        writeln!(
+            f,
+            "{:<10}{:<10}{:>8}{:>8}",
+            "column", "value", "train", "test"
+        )?;
+        for s in self.shares.iter() {
+            writeln!(
+                f,
+                "{:<10}{:<10}{:>7.1}%{:>7.1}%",
+                s.column.to_string(),
+                format_value(&s.value),
+                s.train * 100.0,
+                s.test * 100.0
+            )?;
+        }
+        for (train, test) in self.train.numeric.iter().zip(self.test.numeric.iter()) {
+            writeln!(
+                f,
+                "{:<10}{:<10}{:>8}{:>8}",
+                train.column.to_string(),
+                "mean",
+                format_number(train.mean),
+                format_number(test.mean)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    use crate::loader::{PassengerName, Pclass, Sex};
+    use crate::test_utils;
+
+    fn input_data(sex: Sex, pclass: Pclass, age: Option<f64>, survived: Option<u32>) -> InputData {
+        InputData {
+            survived,
+            pclass: Some(pclass),
+            name: Some(PassengerName::parse("Braund, Mr. Owen Harris")),
+            sex: Some(sex),
+            age,
+            sibsp: Some(0),
+            parch: Some(0),
+            fare: Some(7.25),
+            ..test_utils::input_data(1)
+        }
+    }
+
+    #[fixture]
+    fn fixture_train() -> Vec<InputData> {
+        vec![
+            input_data(Sex::Male, Pclass::Third, Some(22.0), Some(0)),
+            input_data(Sex::Female, Pclass::First, Some(38.0), Some(1)),
+            input_data(Sex::Female, Pclass::Third, Some(26.0), Some(1)),
+            input_data(Sex::Male, Pclass::First, None, Some(1)),
+            input_data(Sex::Male, Pclass::Third, Some(35.0), Some(0)),
+        ]
+    }
+
+    #[rstest]
+    fn test_summarize(fixture_train: Vec<InputData>) {
+        let actual = summarize(Column::Age, &fixture_train);
+        assert_eq!(actual.count, 4);
+        assert_eq!(actual.missing, 1);
+        assert_eq!(actual.mean, Some(30.25));
+        assert_eq!(actual.min, Some(22.0));
+        assert_eq!(actual.q25, Some(25.0));
+        assert_eq!(actual.median, Some(30.5));
+        assert_eq!(actual.max, Some(38.0));
+        assert_eq!(actual.std, Some(7.5));
+    }
+
+    #[rstest]
+    fn test_value_counts(fixture_train: Vec<InputData>) {
+        let actual = value_counts(Column::Embarked, &fixture_train);
+        assert_eq!(
+            actual.counts,
+            vec![ValueCount {
+                value: None,
+                count: 5
+            }]
+        );
+        let actual = value_counts(Column::Sex, &fixture_train);
+        assert_eq!(
+            actual.counts,
+            vec![
+                ValueCount {
+                    value: Some("female".to_string()),
+                    count: 2
+                },
+                ValueCount {
+                    value: Some("male".to_string()),
+                    count: 3
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_survival_rates(fixture_train: Vec<InputData>) {
+        let actual = survival_rates(&[Column::Pclass, Column::Sex], &fixture_train);
+        let actual = actual
+            .groups
+            .iter()
+            .map(|g| (g.key.clone(), g.survived, g.count))
+            .collect::<Vec<_>>();
+        let key = |pclass: &str, sex: &str| vec![Some(pclass.to_string()), Some(sex.to_string())];
+        assert_eq!(
+            actual,
+            vec![
+                (key("1", "female"), 1, 1),
+                (key("1", "male"), 1, 1),
+                (key("3", "female"), 1, 1),
+                (key("3", "male"), 0, 2),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_Profile_new(fixture_train: Vec<InputData>) {
+        let test = vec![input_data(Sex::Female, Pclass::Second, None, None)];
+        let actual = Profile::new(&fixture_train, &test, &GroupBy::defaults());
+        assert_eq!(actual.train.survival.len(), 5);
+        assert!(actual.test.survival.is_empty());
+        let female = actual
+            .shares
+            .iter()
+            .find(|s| s.column == Column::Sex && s.value.as_deref() == Some("female"))
+            .unwrap();
+        assert_eq!((female.train, female.test), (0.4, 1.0));
+        let json = serde_json::to_value(&actual).unwrap();
+        assert_eq!(json["train"]["numeric"][0]["column"], "age");
+    }
+
+    #[rstest]
+    #[case("sex", Ok(GroupBy(vec![Column::Sex])))]
+    #[case("pclass,sex", Ok(GroupBy(vec![Column::Pclass, Column::Sex])))]
+    #[case("pclass,sex,age", Err("expected one or two columns, got \"pclass,sex,age\"".to_string()))]
+    #[case("deck", Err("unknown column: deck".to_string()))]
+    fn test_GroupBy_from_str(#[case] s: &str, #[case] expected: Result<GroupBy, String>) {
+        assert_eq!(s.parse::<GroupBy>(), expected);
+    }
+}
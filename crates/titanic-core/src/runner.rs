@@ -0,0 +1,381 @@
+use std::fs;
+
+use anyhow::Result;
+
+use crate::config::Config;
+use crate::feature::AbstractFeatureTransformer;
+use crate::kfold::AbstractKFold;
+use crate::loader::InputData;
+use crate::metrics::accuracy;
+use crate::model::{check_missing, AbstractGBDTModel, AbstractModel};
+
+#[derive(Debug, Clone)]
+pub struct PredictionResult {
+    pub score: f64,
+    pub valid_passenger_id: Vec<u32>,
+    pub valid_label: Vec<f64>,
+    pub pred_valid: Vec<f64>,
+    pub pred_test: Vec<f64>,
+    pub feature_names: Vec<String>,
+    pub feature_importances: Vec<f64>,
+}
+
+pub trait AbstractRunner {
+    fn run_cv(
+        &mut self,
+        train: &[InputData],
+        labels: Vec<u32>,
+        test: &[InputData],
+    ) -> Result<Vec<PredictionResult>>;
+}
+
+pub fn split_fold(
+    train: &[InputData],
+    mut fold_index: Vec<usize>,
+) -> (Vec<InputData>, Vec<InputData>) {
+    let mut train_fold = train.to_vec();
+    let mut valid_fold = vec![];
+    fold_index.sort();
+    fold_index.reverse();
+    for i in fold_index {
+        train_fold.remove(i);
+        valid_fold.push(train[i].clone());
+    }
+    valid_fold.reverse();
+    (train_fold, valid_fold)
+}
+
+/// Cross-validation runner that works with any model backend.
+///
+/// Feature importances are reported when the model is an [`AbstractGBDTModel`].
+pub struct GbdtRunner<M: AbstractModel + ?Sized = dyn AbstractGBDTModel> {
+    config: Config,
+    feature_transformer: Box<dyn AbstractFeatureTransformer>,
+    kfold: Box<dyn AbstractKFold>,
+    model: Box<M>,
+    save_models: bool,
+}
+
+impl<M: AbstractModel + ?Sized> GbdtRunner<M> {
+    pub fn new(
+        config: Config,
+        feature_transformer: Box<dyn AbstractFeatureTransformer>,
+        kfold: Box<dyn AbstractKFold>,
+        model: Box<M>,
+    ) -> Self {
+        Self {
+            config,
+            feature_transformer,
+            kfold,
+            model,
+            save_models: true,
+        }
+    }
+
+    /// Whether each fold model is saved to `config.output.model_dir()`; on by default.
+    pub fn set_save_models(&mut self, save_models: bool) {
+        self.save_models = save_models;
+    }
+
+    fn run_fold(
+        &mut self,
+        fold: usize,
+        train_fold: &[InputData],
+        valid_fold: &[InputData],
+        test: &[InputData],
+    ) -> Result<PredictionResult> {
+        self.feature_transformer.fit(train_fold)?;
+        let train_features = self.feature_transformer.transform(train_fold)?;
+        let valid_features = self.feature_transformer.transform(valid_fold)?;
+        let test_features = self.feature_transformer.transform(test)?;
+        for features in [&train_features, &valid_features, &test_features] {
+            check_missing(self.model.as_ref(), features)?;
+        }
+        let train_label: Vec<f32> = train_fold
+            .iter()
+            .map(|x| x.survived.unwrap() as f32)
+            .collect();
+        let valid_label: Vec<f64> = valid_fold
+            .iter()
+            .map(|x| x.survived.unwrap() as f64)
+            .collect();
+
+        self.model.train(
+            &train_features,
+            &train_label,
+            Some((
+                &valid_features,
+                &valid_label.iter().map(|x| *x as f32).collect::<Vec<f32>>(),
+            )),
+            None,
+            &self.config.model.params,
+        )?;
+        if self.save_models {
+            fs::create_dir_all(self.config.output.model_dir())?;
+            let model_path = self.config.output.model_path(fold);
+            self.model.save(&model_path.to_string_lossy())?;
+            let feature_transformer_path = self.config.output.feature_transformer_path(fold);
+            self.feature_transformer
+                .save(&feature_transformer_path.to_string_lossy())?;
+        }
+        let pred_valid = self.model.predict(&valid_features)?;
+        let score = accuracy(&valid_label, &pred_valid)?;
+        println!("Accuracy: {:?}", score);
+        // Loaded models may not know their feature names.
+        let schema_names = || {
+            train_features
+                .schema()
+                .names()
+                .into_iter()
+                .map(String::from)
+                .collect()
+        };
+        let (feature_names, feature_importances) = match self.model.as_gbdt() {
+            Some(model) => (
+                model.feature_names()?.unwrap_or_else(schema_names),
+                model.feature_importances()?,
+            ),
+            None => (schema_names(), Vec::new()),
+        };
+
+        let pred_test = self.model.predict(&test_features)?;
+
+        let prediction_result = PredictionResult {
+            score,
+            valid_passenger_id: valid_fold.iter().map(|x| x.passenger_id).collect(),
+            valid_label,
+            pred_valid,
+            pred_test,
+            feature_names,
+            feature_importances,
+        };
+        Ok(prediction_result)
+    }
+}
+
+impl<M: AbstractModel + ?Sized> AbstractRunner for GbdtRunner<M> {
+    fn run_cv(
+        &mut self,
+        train: &[InputData],
+        labels: Vec<u32>,
+        test: &[InputData],
+    ) -> Result<Vec<PredictionResult>> {
+        self.feature_transformer
+            .fit_unlabeled(&[train, test].concat())?;
+        let folds = self.kfold.split(train, labels);
+
+        let mut prediction_results = vec![];
+        for (n_fold, fold_index) in folds.into_iter().enumerate() {
+            println!("Fold {:?}", n_fold + 1);
+
+            let (train_fold, valid_fold) = split_fold(train, fold_index);
+
+            let prediction_result = self.run_fold(n_fold, &train_fold, &valid_fold, test)?;
+            prediction_results.push(prediction_result)
+        }
+        Ok(prediction_results)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    use crate::config::AbstractConfig;
+    use crate::feature::{FeatureField, FeatureMatrix, FeatureSchema, FeatureTransformer};
+    use crate::kfold::KFold;
+    use crate::loader::Pclass;
+    use crate::test_utils;
+
+    /// Predicts the mean training label; has no feature importances.
+    ///
+    /// As a GBDT model it reports no feature names, like a loaded LightGBM model.
+    struct MeanModel {
+        mean: f64,
+        handles_missing: bool,
+        gbdt: bool,
+    }
+
+    impl AbstractModel for MeanModel {
+        fn train(
+            &mut self,
+            _train_features: &FeatureMatrix,
+            train_label: &[f32],
+            _valid: Option<(&FeatureMatrix, &[f32])>,
+            _sample_weight: Option<&[f32]>,
+            _params: &serde_json::Value,
+        ) -> Result<()> {
+            self.mean =
+                train_label.iter().map(|x| *x as f64).sum::<f64>() / train_label.len() as f64;
+            Ok(())
+        }
+
+        fn predict(&self, features: &FeatureMatrix) -> Result<Vec<f64>> {
+            Ok(vec![self.mean; features.n_rows()])
+        }
+
+        fn save(&self, _path: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn load(&mut self, _path: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn handles_missing(&self) -> bool {
+            self.handles_missing
+        }
+
+        fn as_gbdt(&self) -> Option<&dyn AbstractGBDTModel> {
+            self.gbdt.then_some(self as &dyn AbstractGBDTModel)
+        }
+    }
+
+    impl AbstractGBDTModel for MeanModel {
+        fn feature_names(&self) -> Result<Option<Vec<String>>> {
+            Ok(None)
+        }
+
+        fn feature_importances(&self) -> Result<Vec<f64>> {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Names its only feature after the number of rows it was fitted on.
+    struct FitSizeTransformer {
+        n_rows: usize,
+    }
+
+    impl AbstractFeatureTransformer for FitSizeTransformer {
+        fn fit(&mut self, input_data: &[InputData]) -> Result<()> {
+            self.n_rows = input_data.len();
+            Ok(())
+        }
+
+        fn transform(&self, input_data: &[InputData]) -> Result<FeatureMatrix> {
+            let schema = FeatureSchema::new(vec![FeatureField::float(format!(
+                "fitted_on_{}",
+                self.n_rows
+            ))])?;
+            let mut matrix = FeatureMatrix::new(schema);
+            for _ in input_data.iter() {
+                matrix.push_row(&[0.0]);
+            }
+            Ok(matrix)
+        }
+
+        fn state(&self) -> Result<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+
+        fn set_state(&mut self, _state: serde_json::Value) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn input_data(passenger_id: u32) -> InputData {
+        InputData {
+            survived: Some(0),
+            pclass: Some(Pclass::Third),
+            ..test_utils::input_data(passenger_id)
+        }
+    }
+
+    #[rstest]
+    #[case(vec![3, 0], vec![1, 2, 4], vec![0, 3])]
+    #[case(vec![], vec![0, 1, 2, 3, 4], vec![])]
+    fn test_split_fold(
+        #[case] fold_index: Vec<usize>,
+        #[case] expected_train: Vec<u32>,
+        #[case] expected_valid: Vec<u32>,
+    ) {
+        let train = (0..5).map(input_data).collect::<Vec<_>>();
+        let (train_fold, valid_fold) = split_fold(&train, fold_index);
+        let ids = |data: &[InputData]| data.iter().map(|x| x.passenger_id).collect::<Vec<_>>();
+        assert_eq!(ids(&train_fold), expected_train);
+        assert_eq!(ids(&valid_fold), expected_valid);
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn test_GbdtRunner_run_cv_without_feature_names(#[case] gbdt: bool) {
+        let train = (0..10)
+            .map(|i| InputData {
+                survived: Some(u32::from(i < 8)),
+                ..input_data(i)
+            })
+            .collect::<Vec<_>>();
+        let labels = train.iter().map(|x| x.survived.unwrap()).collect();
+        let test = (10..13).map(input_data).collect::<Vec<_>>();
+
+        let mut runner = GbdtRunner::new(
+            Config::new(),
+            Box::new(FeatureTransformer::default()),
+            Box::new(KFold::new(2, false, None)),
+            Box::new(MeanModel {
+                mean: 0.0,
+                handles_missing: true,
+                gbdt,
+            }),
+        );
+        runner.set_save_models(false);
+        let prediction_results = runner.run_cv(&train, labels, &test).unwrap();
+        assert_eq!(prediction_results.len(), 2);
+        for r in prediction_results.iter() {
+            assert_eq!(r.pred_valid.len(), 5);
+            assert_eq!(r.pred_test, vec![0.8; 3]);
+            assert_eq!(r.feature_names.len(), 9);
+            assert!(r.feature_importances.is_empty());
+        }
+    }
+
+    #[rstest]
+    fn test_GbdtRunner_run_cv_fits_on_train_fold() {
+        let train = (0..10).map(input_data).collect::<Vec<_>>();
+        let labels = train.iter().map(|x| x.survived.unwrap()).collect();
+        let test = (10..13).map(input_data).collect::<Vec<_>>();
+
+        let mut runner = GbdtRunner::new(
+            Config::new(),
+            Box::new(FitSizeTransformer { n_rows: 0 }),
+            Box::new(KFold::new(5, false, None)),
+            Box::new(MeanModel {
+                mean: 0.0,
+                handles_missing: true,
+                gbdt: false,
+            }),
+        );
+        runner.set_save_models(false);
+        let prediction_results = runner.run_cv(&train, labels, &test).unwrap();
+        for r in prediction_results.iter() {
+            assert_eq!(r.feature_names, vec!["fitted_on_8"]);
+        }
+    }
+
+    #[rstest]
+    fn test_GbdtRunner_run_cv_missing_values() {
+        let train = (0..10).map(input_data).collect::<Vec<_>>();
+        let labels = train.iter().map(|x| x.survived.unwrap()).collect();
+        let test = (10..13).map(input_data).collect::<Vec<_>>();
+
+        let mut runner = GbdtRunner::new(
+            Config::new(),
+            Box::new(FeatureTransformer::new(&["pclass".to_string(), "age".to_string()]).unwrap()),
+            Box::new(KFold::new(2, false, None)),
+            Box::new(MeanModel {
+                mean: 0.0,
+                handles_missing: false,
+                gbdt: false,
+            }),
+        );
+        runner.set_save_models(false);
+        let actual = runner.run_cv(&train, labels, &test).err().unwrap();
+        assert!(actual
+            .to_string()
+            .starts_with("features [\"age\"] have missing values"));
+    }
+}
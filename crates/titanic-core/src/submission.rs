@@ -0,0 +1,38 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use csv::Writer;
+
+use crate::loader::open_csv;
+
+/// The passenger ids of the sample submission at `path`, in order.
+pub fn load_sample_submission_ids<P: AsRef<Path>>(path: P) -> Result<Vec<u32>> {
+    let path = path.as_ref();
+    open_csv(path)?
+        .records()
+        .map(|r| Ok(r?[0].parse::<u32>()?))
+        .collect::<Result<Vec<_>>>()
+        .with_context(|| format!("failed to read passenger ids from {}", path.display()))
+}
+
+pub fn generate_submission<P: AsRef<Path>>(
+    pred_test: Vec<i32>,
+    input_path: P,
+    output_path: P,
+) -> Result<()> {
+    let mut rdr = open_csv(&input_path)?;
+    if let Some(parent) = output_path.as_ref().parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut wtr = Writer::from_path(output_path)?;
+    for (i, (r, p)) in rdr.records().zip(pred_test).enumerate() {
+        let r = r.unwrap();
+        let id = &r[0];
+        if i == 0 {
+            wtr.write_record(["PassengerId", "Survived"])?;
+        }
+        wtr.write_record([id, &p.to_string()])?;
+    }
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Fixtures shared by the unit tests of several modules.
+
+use std::path::PathBuf;
+
+use rstest::*;
+
+use crate::loader::{Embarked, InputData, PassengerName, Pclass, Sex, Ticket};
+
+/// The first passenger of the training data.
+#[fixture]
+pub fn fixture_input_data() -> InputData {
+    InputData {
+        passenger_id: 1,
+        survived: Some(0),
+        pclass: Some(Pclass::Third),
+        name: Some(PassengerName::parse("Braund, Mr. Owen Harris")),
+        sex: Some(Sex::Male),
+        age: Some(22.0),
+        sibsp: Some(1),
+        parch: Some(0),
+        ticket: Some(Ticket::parse("A/5 21171")),
+        fare: Some(7.25),
+        cabin: None,
+        embarked: Some(Embarked::S),
+    }
+}
+
+/// A passenger with nothing but an id, whose fields the tests set with struct update syntax.
+pub fn input_data(passenger_id: u32) -> InputData {
+    InputData {
+        passenger_id,
+        survived: None,
+        pclass: None,
+        name: None,
+        sex: None,
+        age: None,
+        sibsp: None,
+        parch: None,
+        ticket: None,
+        fare: None,
+        cabin: None,
+        embarked: None,
+    }
+}
+
+/// `name` in a temporary directory of this process that only the tests of `module` write to.
+pub fn temp_path(module: &str, name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("titanic-core-{}-{}", module, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
@@ -0,0 +1,26 @@
+pub fn calc_vec_mean(vec_vec: Vec<Vec<f64>>) -> Vec<f64> {
+    let mut vec_mean: Vec<f64> = vec![];
+    for i in 0..vec_vec[0].len() {
+        let mut sum = 0.0;
+        for feature_importance in vec_vec.iter() {
+            sum += feature_importance[i];
+        }
+        vec_mean.push(sum / vec_vec.iter().len() as f64);
+    }
+    vec_mean
+}
+
+pub fn convert_probability_to_label(probabilities: Vec<f64>) -> Vec<i32> {
+    probabilities.iter().map(|x| i32::from(*x > 0.5)).collect()
+}
+
+/// Linearly interpolated quantile of sorted values.
+pub fn quantile(sorted: &[f64], q: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64))
+}
@@ -0,0 +1,252 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use anyhow::{bail, Result};
+
+use crate::config::{OnViolation, ValidationConfig};
+use crate::loader::InputData;
+
+/// A value that parses but breaks a domain rule of [`ValidationConfig`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    Age {
+        passenger_id: u32,
+        value: f64,
+    },
+    Fare {
+        passenger_id: u32,
+        value: f64,
+    },
+    SibSp {
+        passenger_id: u32,
+        value: i32,
+    },
+    Parch {
+        passenger_id: u32,
+        value: i32,
+    },
+    /// The id appears more than once in one file.
+    DuplicatePassengerId(u32),
+    /// The id appears in both train and test.
+    SharedPassengerId(u32),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Age {
+                passenger_id,
+                value,
+            } => write!(
+                f,
+                "passenger {}: age {} is out of range",
+                passenger_id, value
+            ),
+            Violation::Fare {
+                passenger_id,
+                value,
+            } => write!(f, "passenger {}: fare {} is too low", passenger_id, value),
+            Violation::SibSp {
+                passenger_id,
+                value,
+            } => write!(f, "passenger {}: sibsp {} is too high", passenger_id, value),
+            Violation::Parch {
+                passenger_id,
+                value,
+            } => write!(f, "passenger {}: parch {} is too high", passenger_id, value),
+            Violation::DuplicatePassengerId(passenger_id) => {
+                write!(f, "passenger id {} is duplicated", passenger_id)
+            }
+            Violation::SharedPassengerId(passenger_id) => {
+                write!(f, "passenger id {} is in both train and test", passenger_id)
+            }
+        }
+    }
+}
+
+impl InputData {
+    /// Checks the per-row rules; missing values are not violations.
+    pub fn validate(&self, rules: &ValidationConfig) -> Vec<Violation> {
+        let passenger_id = self.passenger_id;
+        let mut violations = vec![];
+        if let Some(value) = self
+            .age
+            .filter(|v| !(rules.min_age..=rules.max_age).contains(v))
+        {
+            violations.push(Violation::Age {
+                passenger_id,
+                value,
+            });
+        }
+        if let Some(value) = self.fare.filter(|v| *v < rules.min_fare) {
+            violations.push(Violation::Fare {
+                passenger_id,
+                value,
+            });
+        }
+        if let Some(value) = self.sibsp.filter(|v| !(0..=rules.max_sibsp).contains(v)) {
+            violations.push(Violation::SibSp {
+                passenger_id,
+                value,
+            });
+        }
+        if let Some(value) = self.parch.filter(|v| !(0..=rules.max_parch).contains(v)) {
+            violations.push(Violation::Parch {
+                passenger_id,
+                value,
+            });
+        }
+        violations
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ValidationReport {
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    pub fn is_clean(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Fails on violations, or prints them to stderr if `on_violation` is `Warn`.
+    pub fn enforce(&self, on_violation: OnViolation) -> Result<()> {
+        if self.is_clean() {
+            return Ok(());
+        }
+        match on_violation {
+            OnViolation::Error => bail!("data validation failed: {}", self),
+            OnViolation::Warn => eprint!("warning: {}", self),
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} domain rule violations", self.violations.len())?;
+        for violation in self.violations.iter() {
+            writeln!(f, "  {}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks `train` and `test` against `rules`; pass an empty `test` to check a single file.
+pub fn validate(
+    train: &[InputData],
+    test: &[InputData],
+    rules: &ValidationConfig,
+) -> ValidationReport {
+    let mut violations = vec![];
+    for data in [train, test] {
+        let mut seen = HashSet::new();
+        for input_data in data.iter() {
+            violations.extend(input_data.validate(rules));
+            if rules.unique_passenger_id && !seen.insert(input_data.passenger_id) {
+                violations.push(Violation::DuplicatePassengerId(input_data.passenger_id));
+            }
+        }
+    }
+    if rules.disjoint_passenger_ids {
+        let train_ids = train.iter().map(|x| x.passenger_id).collect::<HashSet<_>>();
+        let mut shared = HashSet::new();
+        for input_data in test.iter() {
+            if train_ids.contains(&input_data.passenger_id)
+                && shared.insert(input_data.passenger_id)
+            {
+                violations.push(Violation::SharedPassengerId(input_data.passenger_id));
+            }
+        }
+    }
+    ValidationReport { violations }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+
+    use rstest::*;
+
+    use crate::test_utils;
+
+    fn input_data(passenger_id: u32) -> InputData {
+        InputData {
+            age: Some(30.0),
+            sibsp: Some(0),
+            parch: Some(0),
+            fare: Some(7.25),
+            ..test_utils::input_data(passenger_id)
+        }
+    }
+
+    #[rstest]
+    #[case(|x: &mut InputData| x.age = None, vec![])]
+    #[case(
+        |x: &mut InputData| x.age = Some(-1.0),
+        vec![Violation::Age { passenger_id: 1, value: -1.0 }]
+    )]
+    #[case(
+        |x: &mut InputData| x.age = Some(120.0),
+        vec![Violation::Age { passenger_id: 1, value: 120.0 }]
+    )]
+    #[case(
+        |x: &mut InputData| x.fare = Some(-0.5),
+        vec![Violation::Fare { passenger_id: 1, value: -0.5 }]
+    )]
+    #[case(
+        |x: &mut InputData| { x.sibsp = Some(11); x.parch = Some(-1) },
+        vec![
+            Violation::SibSp { passenger_id: 1, value: 11 },
+            Violation::Parch { passenger_id: 1, value: -1 },
+        ]
+    )]
+    fn test_InputData_validate(
+        #[case] modify: fn(&mut InputData),
+        #[case] expected: Vec<Violation>,
+    ) {
+        let mut x = input_data(1);
+        modify(&mut x);
+        assert_eq!(x.validate(&ValidationConfig::default()), expected);
+    }
+
+    #[rstest]
+    fn test_validate_passenger_ids() {
+        let train = vec![input_data(1), input_data(2), input_data(2)];
+        let test = vec![input_data(2), input_data(3), input_data(3)];
+        let actual = validate(&train, &test, &ValidationConfig::default());
+        assert_eq!(
+            actual.violations,
+            vec![
+                Violation::DuplicatePassengerId(2),
+                Violation::DuplicatePassengerId(3),
+                Violation::SharedPassengerId(2),
+            ]
+        );
+
+        let rules = ValidationConfig {
+            unique_passenger_id: false,
+            disjoint_passenger_ids: false,
+            ..ValidationConfig::default()
+        };
+        assert!(validate(&train, &test, &rules).is_clean());
+    }
+
+    #[rstest]
+    fn test_ValidationReport_enforce() {
+        let report = ValidationReport {
+            violations: vec![Violation::SharedPassengerId(2)],
+        };
+        assert!(report.enforce(OnViolation::Warn).is_ok());
+        let actual = report.enforce(OnViolation::Error).err().unwrap();
+        assert_eq!(
+            actual.to_string(),
+            "data validation failed: 1 domain rule violations\n  passenger id 2 is in both train and test\n"
+        );
+        assert!(ValidationReport::default()
+            .enforce(OnViolation::Error)
+            .is_ok());
+    }
+}